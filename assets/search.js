@@ -0,0 +1,26 @@
+// opens the crate search page with the entered search term
+const searchInput = document.getElementById('searchInput');
+const searchButton = document.getElementById('searchButton');
+
+const searchPage = searchInput.dataset.searchPage;
+
+searchInput.addEventListener('input', () => {
+    if (searchInput.value.trim() !== '') {
+        searchButton.style.display = 'block';
+    } else {
+        searchButton.style.display = 'none';
+    }
+});
+
+searchButton.addEventListener('click', () => {
+    const searchTerm = searchInput.value.trim();
+    if (searchTerm !== '') {
+        window.location.href = searchPage + `?search=${encodeURIComponent(searchTerm)}`;
+    }
+});
+
+searchInput.addEventListener('keydown', (event) => {
+    if (event.key === 'Enter') {
+        searchButton.click();
+    }
+});
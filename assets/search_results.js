@@ -0,0 +1,249 @@
+// searches the crate-wide index of `search-index.js` for the term of the page url
+// a `fn:`, `struct:`, `trait:`, `global:`, `type:`, `mod:`, `contract:`, `method:` or `field:` prefix limits the kind of the results
+// a query with `->`, e.g. `Field, Point -> bool`, searches functions by their parameter and return types
+const FILTERS = {
+    fn: ['fn', 'method'],
+    struct: ['struct'],
+    trait: ['trait'],
+    global: ['global'],
+    type: ['type'],
+    mod: ['mod'],
+    contract: ['contract'],
+    method: ['method'],
+    field: ['field'],
+};
+
+const MAX_RESULTS = 200;
+
+const searchInput = document.getElementById('searchInput');
+const resultsDiv = document.getElementById('results');
+
+function parseQuery(query) {
+    const match = query.trim().match(/^(\w+):\s*(.*)$/);
+    if (match && FILTERS[match[1]]) {
+        return { kinds: FILTERS[match[1]], term: match[2].trim().toLowerCase() };
+    }
+
+    return { kinds: null, term: query.trim().toLowerCase() };
+}
+
+// scores how well the name matches the term, 0 if it does not match at all
+function nameScore(name, term) {
+    name = name.toLowerCase();
+    if (name === term) {
+        return 100;
+    }
+    if (name.startsWith(term)) {
+        return 80;
+    }
+    if (name.includes(term)) {
+        return 60;
+    }
+
+    // fuzzy match: the characters of the term appear in the name in the same order,
+    // the fewer characters between them the better
+    let position = -1;
+    let gaps = 0;
+    for (const char of term) {
+        const next = name.indexOf(char, position + 1);
+        if (next === -1) {
+            return 0;
+        }
+        if (position !== -1) {
+            gaps += next - position - 1;
+        }
+        position = next;
+    }
+
+    return Math.max(20, 50 - gaps);
+}
+
+// full-text match: every word of the term appears in the doc comment
+function docScore(doc, term) {
+    const words = term.split(/\s+/).filter((word) => word !== '');
+    doc = doc.toLowerCase();
+
+    return words.length > 0 && words.every((word) => doc.includes(word)) ? 10 : 0;
+}
+
+// nesting depth change of the character at the index, the `>` of an `->` arrow is not a bracket
+function bracketDepth(text, i) {
+    const char = text[i];
+    if ('([{<'.includes(char)) {
+        return 1;
+    }
+    if (char === '>' && text[i - 1] === '-') {
+        return 0;
+    }
+
+    return ')]}>'.includes(char) ? -1 : 0;
+}
+
+// splits a list of types at the commas that are not nested in brackets
+function splitTypes(text) {
+    const types = [];
+    let depth = 0;
+    let start = 0;
+
+    for (let i = 0; i < text.length; i++) {
+        depth += bracketDepth(text, i);
+        if (text[i] === ',' && depth === 0) {
+            types.push(text.slice(start, i));
+            start = i + 1;
+        }
+    }
+    types.push(text.slice(start));
+
+    return types.map(normalizeType).filter((type) => type !== '');
+}
+
+function normalizeType(type) {
+    return type.replace(/\s+/g, '').toLowerCase();
+}
+
+// a query type matches the type itself or, without generics, any instance of it, e.g. `Point` matches `Point<Field>`
+function typeMatches(query, type) {
+    type = normalizeType(type);
+
+    return type === query || type.replace(/<.*>$/, '') === query;
+}
+
+// index of the last `->` that is not nested in brackets, -1 if there is none
+// the output of `fn(u8) -> bool -> Field` is `Field`
+function topLevelArrow(term) {
+    let depth = 0;
+    let arrow = -1;
+
+    for (let i = 0; i < term.length; i++) {
+        if (depth === 0 && term.startsWith('->', i)) {
+            arrow = i;
+        }
+        depth += bracketDepth(term, i);
+    }
+
+    return arrow;
+}
+
+// the types before the arrow are the inputs, a query with only nested arrows lists inputs
+function parseTypeQuery(term) {
+    const arrow = topLevelArrow(term);
+    if (arrow === -1) {
+        return { inputs: splitTypes(term), output: '' };
+    }
+
+    return { inputs: splitTypes(term.slice(0, arrow)), output: normalizeType(term.slice(arrow + 2)) };
+}
+
+// scores a function whose types contain the query types, 0 for other items
+// the score is higher when the query lists all parameters of the function
+function typeScore(entry, query) {
+    if (entry.kind !== 'fn' && entry.kind !== 'method') {
+        return 0;
+    }
+    if (query.output !== '' && !typeMatches(query.output, entry.output || '')) {
+        return 0;
+    }
+
+    const remaining = [...(entry.inputs || [])];
+    for (const input of query.inputs) {
+        const index = remaining.findIndex((type) => typeMatches(input, type));
+        if (index === -1) {
+            return 0;
+        }
+        remaining.splice(index, 1);
+    }
+
+    return remaining.length === 0 ? 100 : 70;
+}
+
+function score(entry, term) {
+    if (term === '') {
+        return 1;
+    }
+    if (term.includes('->')) {
+        return typeScore(entry, parseTypeQuery(term));
+    }
+
+    return Math.max(nameScore(entry.name, term), docScore(entry.doc, term));
+}
+
+function search(query) {
+    const { kinds, term } = parseQuery(query);
+
+    return window.searchIndex
+        .filter((entry) => !kinds || kinds.includes(entry.kind))
+        .map((entry) => ({ entry, score: score(entry, term) }))
+        .filter((result) => result.score > 0)
+        .sort((a, b) => b.score - a.score
+            || a.entry.name.length - b.entry.name.length
+            || a.entry.path.localeCompare(b.entry.path))
+        .map((result) => result.entry);
+}
+
+function createTable(results) {
+    const table = document.createElement('table');
+    table.innerHTML = `
+        <tr>
+            <th>Name</th>
+            <th>Type</th>
+            <th>Doc</th>
+        </tr>
+    `;
+
+    results.slice(0, MAX_RESULTS).forEach((result) => {
+        const row = document.createElement('tr');
+
+        const name = document.createElement('td');
+        const link = document.createElement('a');
+        link.href = result.link;
+        link.textContent = `${result.path}::${result.name}`;
+        name.appendChild(link);
+        if (result.deprecated) {
+            const badge = document.createElement('span');
+            badge.className = 'badge deprecated';
+            badge.textContent = 'deprecated';
+            name.appendChild(badge);
+        }
+
+        const kind = document.createElement('td');
+        kind.textContent = result.kind;
+        if (result.inputs || result.output) {
+            const types = document.createElement('code');
+            types.textContent = ` (${(result.inputs || []).join(', ')}) -> ${result.output || '()'}`;
+            kind.appendChild(types);
+        }
+
+        const summary = document.createElement('td');
+        summary.innerHTML = result.summary;
+
+        row.append(name, kind, summary);
+        table.appendChild(row);
+    });
+
+    return table;
+}
+
+function performSearch(query) {
+    resultsDiv.innerHTML = '';
+    if (query.trim() === '') {
+        return;
+    }
+
+    const results = search(query);
+    if (results.length > 0) {
+        resultsDiv.appendChild(createTable(results));
+    } else {
+        resultsDiv.textContent = 'No results';
+    }
+}
+
+searchInput.addEventListener('input', () => {
+    const url = new URL(window.location);
+    url.searchParams.set('search', searchInput.value);
+    window.history.replaceState(null, '', url);
+
+    performSearch(searchInput.value);
+});
+
+searchInput.value = new URLSearchParams(window.location.search).get('search') || '';
+performSearch(searchInput.value);
@@ -0,0 +1,27 @@
+// highlights the lines of a `#L<start>-L<end>` link and scrolls to the first one
+function highlightLines() {
+    document.querySelectorAll('.highlighted').forEach((line) => line.classList.remove('highlighted'));
+
+    const match = window.location.hash.match(/^#L(\d+)(?:-L(\d+))?$/);
+    if (!match) {
+        return;
+    }
+
+    const start = parseInt(match[1]);
+    const end = match[2] ? parseInt(match[2]) : start;
+
+    for (let number = start; number <= end; number++) {
+        const line = document.getElementById('L' + number);
+        if (line) {
+            line.classList.add('highlighted');
+        }
+    }
+
+    const first = document.getElementById('L' + start);
+    if (first) {
+        first.scrollIntoView();
+    }
+}
+
+window.addEventListener('hashchange', highlightLines);
+highlightLines();
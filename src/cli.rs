@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::{CoverageFormat, Level, Lint, LintFormat, OutputFormat};
+
+/// command line interface of the documentation generator
+#[derive(Debug, Parser)]
+#[command(name = "doc-generator", version, about = "Generates documentation for Noir code")]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub(crate) command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum Command {
+    /// generates the documentation pages
+    Build(BuildArgs),
+    /// prints the collected documentation information
+    Map(MapArgs),
+    /// checks the doc comments for missing docs and malformed comments
+    Lint(LintArgs),
+    /// prints how many items of every module are documented
+    Coverage(CoverageArgs),
+    /// checks the ```noir code blocks of the doc comments
+    Test(TestArgs),
+    /// removes the generated documentation
+    Clean(CleanArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct BuildArgs {
+    /// Nargo package or workspace directory, used instead of the entry file
+    #[arg(short, long, conflicts_with_all = ["entry", "source_root"])]
+    pub(crate) program_dir: Option<PathBuf>,
+
+    /// file with a Noir code the documentation is generated for
+    #[arg(short, long, default_value = "input_files/prog.nr")]
+    pub(crate) entry: PathBuf,
+
+    /// directory the source pages and the json spans are relative to, the directory of the entry file by default
+    #[arg(short, long)]
+    pub(crate) source_root: Option<PathBuf>,
+
+    /// directory the documentation files are written to
+    #[arg(short, long, default_value = "generated_doc")]
+    pub(crate) output_dir: PathBuf,
+
+    /// format of the generated documentation
+    #[arg(long, value_enum, default_value_t = OutputFormat::Html)]
+    pub(crate) output_format: OutputFormat,
+
+    /// writes the markdown documentation of a crate to a single `<crate name>.md` file
+    #[arg(long)]
+    pub(crate) single_file: bool,
+
+    /// how links of the generated pages pointing at missing pages or anchors are reported
+    #[arg(long, value_enum, default_value_t = Level::Warn)]
+    pub(crate) broken_links: Level,
+
+    /// documents private and `pub(crate)` items as well
+    #[arg(long)]
+    pub(crate) document_private_items: bool,
+
+    /// lists the `#[test]` functions in a separate section of the module pages
+    #[arg(long)]
+    pub(crate) document_tests: bool,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct MapArgs {
+    /// Nargo package or workspace directory, used instead of the entry file
+    #[arg(short, long, conflicts_with = "entry")]
+    pub(crate) program_dir: Option<PathBuf>,
+
+    /// file with a Noir code the information is collected from
+    #[arg(short, long, default_value = "input_files/prog.nr")]
+    pub(crate) entry: PathBuf,
+
+    /// deprecated, modules are looked up next to the file declaring them
+    #[arg(short, long, hide = true)]
+    pub(crate) source_root: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct LintArgs {
+    /// Nargo package or workspace directory, used instead of the entry file
+    #[arg(short, long, conflicts_with = "entry")]
+    pub(crate) program_dir: Option<PathBuf>,
+
+    /// file with a Noir code the doc comments are checked in
+    #[arg(short, long, default_value = "input_files/prog.nr")]
+    pub(crate) entry: PathBuf,
+
+    /// lints that are not reported
+    #[arg(short = 'A', long, value_enum)]
+    pub(crate) allow: Vec<Lint>,
+
+    /// lints that are reported as warnings, every lint warns by default
+    #[arg(short = 'W', long, value_enum)]
+    pub(crate) warn: Vec<Lint>,
+
+    /// lints that are reported as errors and fail the command
+    #[arg(short = 'D', long, value_enum)]
+    pub(crate) deny: Vec<Lint>,
+
+    /// format of the diagnostics
+    #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+    pub(crate) format: LintFormat,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CoverageArgs {
+    /// Nargo package or workspace directory, used instead of the entry file
+    #[arg(short, long, conflicts_with = "entry")]
+    pub(crate) program_dir: Option<PathBuf>,
+
+    /// file with a Noir code the coverage is measured for
+    #[arg(short, long, default_value = "input_files/prog.nr")]
+    pub(crate) entry: PathBuf,
+
+    /// format of the report
+    #[arg(long, value_enum, default_value_t = CoverageFormat::Table)]
+    pub(crate) format: CoverageFormat,
+
+    /// minimal coverage of every package in percent, the command fails below it
+    #[arg(long, value_parser = min_coverage)]
+    pub(crate) min_coverage: Option<f64>,
+}
+
+fn min_coverage(value: &str) -> Result<f64, String> {
+    match value.trim_end_matches('%').parse::<f64>() {
+        Ok(percentage) if (0.0..=100.0).contains(&percentage) => Ok(percentage),
+        _ => Err(format!("`{}` is not a percentage between 0 and 100", value)),
+    }
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct TestArgs {
+    /// Nargo package or workspace directory, used instead of the entry file
+    #[arg(short, long, conflicts_with = "entry")]
+    pub(crate) program_dir: Option<PathBuf>,
+
+    /// file with a Noir code the doc tests are collected from
+    #[arg(short, long, default_value = "input_files/prog.nr")]
+    pub(crate) entry: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CleanArgs {
+    /// directory with the generated documentation
+    #[arg(short, long, default_value = "generated_doc")]
+    pub(crate) output_dir: PathBuf,
+}
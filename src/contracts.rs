@@ -0,0 +1,117 @@
+use crate::{FunctionAttributes, Info, ItemVisibility, Output, Type};
+
+/// sections of the contract functions, named after the attributes and modifiers that put a function in them
+/// a function is listed in every section it belongs to, e.g. a private initializer
+const FUNCTION_SECTIONS: [(&str, &[&str]); 5] = [
+    ("Private functions", &["private"]),
+    ("Public functions", &["public"]),
+    ("Unconstrained and view functions", &["unconstrained", "view", "utility"]),
+    ("Initializers", &["initializer"]),
+    ("Internal functions", &["internal"]),
+];
+
+/// sections of the contract items that are neither its functions nor its storage, events and notes
+const ITEM_SECTIONS: [(Type, &str); 6] = [
+    (Type::Struct, "Structs"),
+    (Type::Trait, "Traits"),
+    (Type::Global, "Globals"),
+    (Type::TypeAlias, "Type aliases"),
+    (Type::Module, "Modules"),
+    (Type::Contract, "Contracts"),
+];
+
+/// a titled group of items on a module or contract page
+#[derive(Debug, Clone)]
+pub(crate) struct ItemSection {
+    pub(crate) title: String,
+    pub(crate) items: Vec<Output>,
+}
+
+/// name of an attribute without its arguments, Aztec attributes are unwrapped
+/// e.g. `#[aztec(private)]` and `#[private]` give `private`, `#[oracle(get_notes)]` gives `oracle`
+pub(crate) fn attribute_name(attribute: &str) -> &str {
+    let attribute = attribute.trim().trim_start_matches("#[").trim_end_matches(']');
+    let attribute = attribute.strip_prefix("aztec(").and_then(|inner| inner.strip_suffix(')')).unwrap_or(attribute);
+
+    attribute.split('(').next().unwrap_or_default().trim()
+}
+
+/// the modifiers and the attribute names a contract function is grouped by
+fn function_markers(attributes: &FunctionAttributes) -> Vec<&str> {
+    attributes.modifiers.iter().map(String::as_str)
+        .chain(attributes.attributes.iter().map(|attribute| attribute_name(attribute)))
+        .collect()
+}
+
+/// titles of the function sections the item belongs to, empty for other items
+fn function_sections(item: &Output) -> Vec<&'static str> {
+    let Info::Function { attributes, .. } = &item.information else {
+        return Vec::new();
+    };
+    let markers = function_markers(attributes);
+
+    FUNCTION_SECTIONS.iter()
+        .filter(|(_, names)| markers.iter().any(|marker| names.contains(marker)))
+        .map(|(title, _)| *title)
+        .collect()
+}
+
+fn has_attribute(item: &Output, name: &str) -> bool {
+    matches!(&item.information, Info::Struct { attributes, .. } if attributes.iter().any(|attribute| attribute_name(attribute) == name))
+}
+
+/// the struct with the state of the contract, marked with `#[storage]` or named `Storage`
+fn is_storage(item: &Output) -> bool {
+    item.r#type == Type::Struct && (item.name == "Storage" || has_attribute(item, "storage"))
+}
+
+fn is_event(item: &Output) -> bool {
+    item.r#type == Type::Struct && has_attribute(item, "event")
+}
+
+/// notes are marked with `#[note]` or implement the `NoteInterface` trait
+fn is_note(item: &Output) -> bool {
+    let implements_note = item.information.get_implementations().unwrap_or_default().iter()
+        .filter_map(|implementation| implementation.trait_name.as_ref())
+        .any(|name| name.rsplit("::").next() == Some("NoteInterface"));
+
+    item.r#type == Type::Struct && (has_attribute(item, "note") || implements_note)
+}
+
+/// the entry points, storage, events and notes are the interface of a contract, they are public without `pub`
+pub(crate) fn contract_visibility(items: Vec<Output>) -> Vec<Output> {
+    items.into_iter()
+        .map(|mut item| {
+            let is_interface = !function_sections(&item).is_empty() || is_storage(&item) || is_event(&item) || is_note(&item);
+            if is_interface && item.visibility == ItemVisibility::Private {
+                item.visibility = ItemVisibility::Public;
+            }
+
+            item
+        })
+        .collect()
+}
+
+/// groups the items of a contract: the storage, the functions by their attributes, the events and notes, then the other items
+/// empty sections are left out
+pub(crate) fn contract_sections(items: &[Output]) -> Vec<ItemSection> {
+    let section = |title: &str, filter: &dyn Fn(&Output) -> bool| ItemSection {
+        title: title.to_string(),
+        items: items.iter().filter(|item| filter(item)).cloned().collect(),
+    };
+    let is_role = |item: &Output| is_storage(item) || is_event(item) || is_note(item);
+
+    let mut res = vec![section("Storage", &is_storage)];
+    for (title, _) in FUNCTION_SECTIONS.iter() {
+        res.push(section(title, &|item| !item.is_test() && function_sections(item).contains(title)));
+    }
+    res.push(section("Other functions", &|item| item.r#type == Type::Function && !item.is_test() && function_sections(item).is_empty()));
+    res.push(section("Events", &|item| is_event(item) && !is_storage(item)));
+    res.push(section("Notes", &|item| is_note(item) && !is_storage(item) && !is_event(item)));
+    for (r#type, title) in ITEM_SECTIONS.iter() {
+        res.push(section(title, &|item| item.r#type == *r#type && !is_role(item)));
+    }
+    res.push(section("Tests", &Output::is_test));
+
+    res.into_iter().filter(|section| !section.items.is_empty()).collect()
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{is_documented, Function, Info, Output, Type};
+
+/// how the coverage report is printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoverageFormat {
+    /// a table per package
+    Table,
+    /// a json array with all packages
+    Json,
+}
+
+/// number of documented items of one kind out of all of them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) struct Count {
+    pub(crate) documented: usize,
+    pub(crate) total: usize,
+}
+
+impl Count {
+    fn add(&mut self, documented: bool) {
+        self.total += 1;
+        if documented {
+            self.documented += 1;
+        }
+    }
+
+    fn merge(&mut self, other: Count) {
+        self.documented += other.documented;
+        self.total += other.total;
+    }
+
+    /// share of the documented items in percent, nothing to document counts as fully documented
+    pub(crate) fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        }
+        else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.documented, self.total)
+    }
+}
+
+/// documented items of every kind
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Counts {
+    pub(crate) functions: Count,
+    pub(crate) structs: Count,
+    pub(crate) fields: Count,
+    pub(crate) traits: Count,
+    pub(crate) trait_methods: Count,
+    pub(crate) impl_methods: Count,
+}
+
+impl Counts {
+    fn kinds(&self) -> [Count; 6] {
+        [self.functions, self.structs, self.fields, self.traits, self.trait_methods, self.impl_methods]
+    }
+
+    pub(crate) fn total(&self) -> Count {
+        let mut res = Count::default();
+        for count in self.kinds() {
+            res.merge(count);
+        }
+
+        res
+    }
+
+    fn merge(&mut self, other: &Counts) {
+        self.functions.merge(other.functions);
+        self.structs.merge(other.structs);
+        self.fields.merge(other.fields);
+        self.traits.merge(other.traits);
+        self.trait_methods.merge(other.trait_methods);
+        self.impl_methods.merge(other.impl_methods);
+    }
+}
+
+/// coverage of the items declared directly in a module
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct ModuleCoverage {
+    /// path of the module, e.g. `my_crate::shapes`
+    pub(crate) path: String,
+    #[serde(flatten)]
+    pub(crate) counts: Counts,
+    pub(crate) coverage: f64,
+}
+
+/// coverage of a package with all its modules
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct PackageCoverage {
+    pub(crate) name: String,
+    pub(crate) modules: Vec<ModuleCoverage>,
+    pub(crate) total: Counts,
+    pub(crate) coverage: f64,
+}
+
+impl PackageCoverage {
+    /// counts the documented items of every module of the crate
+    pub(crate) fn new(name: &str, items: &[Output]) -> Self {
+        let mut modules = Vec::new();
+        collect_modules(&mut modules, name, items);
+
+        let mut total = Counts::default();
+        for module in modules.iter() {
+            total.merge(&module.counts);
+        }
+        let coverage = total.total().percentage();
+
+        PackageCoverage { name: name.to_string(), modules, total, coverage }
+    }
+
+    /// the coverage report as a table with a row per module
+    pub(crate) fn table(&self) -> String {
+        let header = ["Module", "Functions", "Structs", "Fields", "Traits", "Trait methods", "Impl methods", "Coverage"];
+
+        let row = |path: &str, counts: &Counts| {
+            let mut row = vec![path.to_string()];
+            row.extend(counts.kinds().iter().map(|count| count.to_string()));
+            row.push(format!("{:.1}%", counts.total().percentage()));
+            row
+        };
+        let mut rows = vec![header.iter().map(|column| column.to_string()).collect::<Vec<_>>()];
+        rows.extend(self.modules.iter().map(|module| row(&module.path, &module.counts)));
+        rows.push(row("Total", &self.total));
+
+        let widths = (0..header.len()).map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or_default()).collect::<Vec<_>>();
+
+        let mut res = String::new();
+        for row in rows.iter() {
+            let line = row.iter().zip(widths.iter()).enumerate().map(|(i, (cell, width))| {
+                // the module column is aligned to the left, the numbers to the right
+                if i == 0 { format!("{:<width$}", cell) } else { format!("{:>width$}", cell) }
+            }).collect::<Vec<_>>().join("  ");
+
+            res.push_str(line.trim_end());
+            res.push('\n');
+        }
+
+        res
+    }
+}
+
+fn collect_modules(res: &mut Vec<ModuleCoverage>, path: &str, items: &[Output]) {
+    let mut counts = Counts::default();
+    let start = res.len();
+    res.push(ModuleCoverage { path: path.to_string(), counts: Counts::default(), coverage: 0.0 });
+
+    for item in items.iter() {
+        match (&item.r#type, &item.information) {
+            (Type::Function, _) => counts.functions.add(is_documented(item)),
+            (Type::Struct, Info::Struct { fields, implementations, .. }) => {
+                counts.structs.add(is_documented(item));
+                for field in fields.iter() {
+                    counts.fields.add(!field.doc.trim().is_empty());
+                }
+                // methods of trait implementations are documented by the trait
+                for implementation in implementations.iter().filter(|implementation| implementation.trait_name.is_none()) {
+                    add_methods(&mut counts.impl_methods, &implementation.functions);
+                }
+            }
+            (Type::Trait, Info::Trait { required_methods, provided_methods, .. }) => {
+                counts.traits.add(is_documented(item));
+                add_methods(&mut counts.trait_methods, required_methods);
+                add_methods(&mut counts.trait_methods, provided_methods);
+            }
+            (Type::Module | Type::Contract, Info::Module { content, .. }) => collect_modules(res, &format!("{}::{}", path, item.name), content),
+            _ => {}
+        }
+    }
+
+    res[start].coverage = counts.total().percentage();
+    res[start].counts = counts;
+}
+
+fn add_methods(count: &mut Count, methods: &[Function]) {
+    for method in methods.iter() {
+        count.add(!method.doc.trim().is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use std::{fmt, fs, path::{Path, PathBuf}};
+
+use noirc_driver::{add_dep, check_crate, file_manager_with_stdlib, prepare_crate, prepare_dependency};
+use noirc_frontend::{graph::CrateName, hir::Context, parser::parse_program};
+
+use crate::{DocSpan, Function, Info, Output, Type, line, module_files, unindent};
+
+/// keywords of the lines that make a doc test a complete program rather than a body of `main`
+const ITEM_KEYWORDS: [&str; 10] = ["fn ", "pub ", "struct ", "trait ", "impl", "use ", "mod ", "global ", "contract ", "#["];
+
+/// a ```noir code block of a doc comment
+#[derive(Debug, PartialEq)]
+pub(crate) struct DocTest {
+    /// path of the item the doc comment belongs to, e.g. `my_crate::shapes::Point`
+    pub(crate) item: String,
+    pub(crate) file: PathBuf,
+    /// line of the first line of code in the file
+    pub(crate) line: usize,
+    /// code of the block with the hidden `# ` lines
+    pub(crate) code: String,
+    pub(crate) ignore: bool,
+    pub(crate) should_fail: bool,
+}
+
+/// the documented crate, the doc tests depend on it and bring its items into scope with `use dep::<name>::...`
+pub(crate) struct TestCrate {
+    pub(crate) name: String,
+    pub(crate) entry: PathBuf,
+    /// the files of the crate modules with their text, read once for all doc tests
+    pub(crate) files: Vec<(PathBuf, String)>,
+}
+
+impl TestCrate {
+    pub(crate) fn new(name: &str, entry: &Path, items: &[Output]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut paths = vec![entry.to_path_buf()];
+        module_files(&mut paths, items);
+
+        let mut files = Vec::new();
+        for path in paths.into_iter() {
+            let text = fs::read_to_string(&path).map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+            files.push((path, text));
+        }
+
+        Ok(TestCrate { name: name.to_string(), entry: entry.to_path_buf(), files })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DocTestResult {
+    Passed,
+    Failed(String),
+    Ignored,
+}
+
+impl fmt::Display for DocTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}:{})", self.item, self.file.display(), self.line)
+    }
+}
+
+impl DocTest {
+    /// the code as a program, statements are wrapped into `fn main`
+    /// returns the program with the number of lines added before the code
+    pub(crate) fn program(&self) -> (String, usize) {
+        let is_program = self.code.lines().any(|line| ITEM_KEYWORDS.iter().any(|keyword| line.starts_with(keyword)));
+
+        if is_program {
+            (self.code.clone(), 0)
+        }
+        else {
+            (format!("fn main() {{\n{}\n}}\n", self.code), 1)
+        }
+    }
+
+    /// line of the byte offset of the program in the file of the doc comment
+    fn location(&self, program: &str, offset: usize, position: u32) -> String {
+        let line = program[..(position as usize).min(program.len())].matches('\n').count();
+
+        format!("{}:{}", self.file.display(), self.line + line.saturating_sub(offset))
+    }
+
+    /// checks the program of the doc test the way the compiler does: it is parsed, its definitions are collected,
+    /// its names are resolved and its types are checked, with the documented crate as a dependency
+    /// a `should_fail` test passes when the checks fail, code that does not parse always fails
+    pub(crate) fn run(&self, krate: &TestCrate) -> DocTestResult {
+        if self.ignore {
+            return DocTestResult::Ignored;
+        }
+
+        let (program, offset) = self.program();
+        let (_, errors) = parse_program(&program);
+        if let Some(err) = errors.first() {
+            return DocTestResult::Failed(format!("{}: {}", self.location(&program, offset, err.span().start()), err));
+        }
+
+        let errors = match self.check(krate, &program) {
+            Ok(errors) => errors,
+            Err(err) => return DocTestResult::Failed(err),
+        };
+
+        match (errors.first(), self.should_fail) {
+            (None, false) | (Some(_), true) => DocTestResult::Passed,
+            (None, true) => DocTestResult::Failed("the code compiled, but the test is marked as `should_fail`".to_string()),
+            (Some((position, message)), false) => DocTestResult::Failed(format!("{}: {}", self.location(&program, offset, *position), message)),
+        }
+    }
+
+    /// runs the checks of the compiler over the program, returns the errors in it with their byte offsets
+    /// errors in the documented crate itself are returned as `Err`, they do not depend on the doc test
+    fn check(&self, krate: &TestCrate, program: &str) -> Result<Vec<(u32, String)>, String> {
+        let absolute = |path: &Path| fs::canonicalize(path).map_err(|err| format!("failed to open {}: {}", path.display(), err));
+        let entry = absolute(&krate.entry)?;
+        let root = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let mut file_manager = file_manager_with_stdlib(&root);
+        let mut crate_files = Vec::new();
+        for (path, text) in krate.files.iter() {
+            if let Some(file_id) = file_manager.add_file_with_source(&absolute(path)?, text.clone()) {
+                crate_files.push((file_id, path, text));
+            }
+        }
+        // the program is not written to disk, it gets a path of its own next to the crate entry
+        let test_path = root.join(format!("{}_doc_test.nr", krate.name));
+        let test_file = file_manager.add_file_with_source(&test_path, program.to_string())
+            .ok_or_else(|| format!("failed to add the doc test of {} to the compiler", self.item))?;
+
+        let crate_name = krate.name.parse::<CrateName>().map_err(|err| format!("invalid crate name `{}`: {}", krate.name, err))?;
+        let mut context = Context::new(file_manager);
+        let crate_id = prepare_dependency(&mut context, &entry);
+        let test_crate = prepare_crate(&mut context, &test_path);
+        add_dep(&mut context, test_crate, crate_id, crate_name);
+
+        let Err(diagnostics) = check_crate(&mut context, test_crate, false, false) else {
+            return Ok(Vec::new());
+        };
+
+        let mut res = Vec::new();
+        for diagnostic in diagnostics.iter().filter(|diagnostic| diagnostic.diagnostic.is_error()) {
+            let message = diagnostic.diagnostic.message.clone();
+            let position = diagnostic.diagnostic.secondaries.first().map(|label| label.span.start()).unwrap_or_default();
+
+            if diagnostic.file_id == test_file {
+                res.push((position, message));
+            }
+            else {
+                let location = match crate_files.iter().find(|(file_id, ..)| *file_id == diagnostic.file_id) {
+                    Some((_, path, text)) => format!("{}:{}", path.display(), line(text, position)),
+                    None => krate.entry.display().to_string(),
+                };
+                return Err(format!("the documented crate does not compile: {}: {}", location, message));
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// a fenced code block of a doc comment
+pub(crate) struct CodeBlock {
+    /// line of the opening fence inside the doc comment
+    pub(crate) fence_line: usize,
+    info: String,
+    lines: Vec<String>,
+    /// an unclosed block runs to the end of the doc comment
+    pub(crate) closed: bool,
+}
+
+/// finds the fenced code blocks of a doc comment
+pub(crate) fn code_blocks(doc: &str) -> Vec<CodeBlock> {
+    let mut res = Vec::new();
+    let mut current: Option<(String, CodeBlock)> = None;
+
+    for (i, line) in doc.lines().enumerate() {
+        let trimmed = line.trim_start();
+
+        match current.take() {
+            Some((fence, mut block)) if trimmed.starts_with(&fence) && trimmed[fence.len()..].trim().is_empty() => {
+                block.closed = true;
+                res.push(block);
+            }
+            Some((fence, mut block)) => {
+                block.lines.push(line.to_string());
+                current = Some((fence, block));
+            }
+            None => {
+                let fence_len = trimmed.chars().take_while(|c| *c == '`').count().max(trimmed.chars().take_while(|c| *c == '~').count());
+                if fence_len >= 3 {
+                    let fence = trimmed[..fence_len].to_string();
+                    let info = trimmed[fence_len..].trim().to_string();
+
+                    current = Some((fence, CodeBlock { fence_line: i, info, lines: Vec::new(), closed: false }));
+                }
+            }
+        }
+    }
+    res.extend(current.map(|(_, block)| block));
+
+    res
+}
+
+/// the language and the attributes of a code block, e.g. `noir,ignore` or `noir should_fail`
+fn block_attributes(info: &str) -> Vec<&str> {
+    info.split(|c: char| c == ',' || c.is_whitespace()).filter(|attribute| !attribute.is_empty()).collect()
+}
+
+/// a hidden line of a doc test is compiled but not shown, returns its code
+fn hidden_line(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+
+    if trimmed == "#" {
+        Some("")
+    }
+    else {
+        trimmed.strip_prefix("# ")
+    }
+}
+
+/// extracts the doc tests of a doc comment written at the span
+fn doc_comment_tests(doc: &str, item: &str, span: &DocSpan) -> Vec<DocTest> {
+    let doc = unindent(doc);
+
+    code_blocks(&doc).into_iter().filter_map(|block| {
+        let attributes = block_attributes(&block.info);
+        if attributes.first() != Some(&"noir") {
+            return None;
+        }
+
+        let code = block.lines.iter().map(|line| hidden_line(line).unwrap_or(line)).collect::<Vec<_>>().join("\n");
+
+        Some(DocTest {
+            item: item.to_string(),
+            file: span.file.clone(),
+            line: span.line(block.fence_line + 1),
+            code,
+            ignore: attributes.contains(&"ignore"),
+            should_fail: attributes.contains(&"should_fail"),
+        })
+    }).collect()
+}
+
+/// removes the hidden lines of the ```noir code blocks and the test attributes from their info strings
+/// used when the doc comment is shown
+pub(crate) fn hide_doc_test_lines(doc: &str) -> String {
+    let blocks = code_blocks(doc);
+    let mut hidden = Vec::new();
+    let mut fences = Vec::new();
+
+    for block in blocks.iter().filter(|block| block_attributes(&block.info).first() == Some(&"noir")) {
+        fences.push(block.fence_line);
+        for (i, line) in block.lines.iter().enumerate() {
+            if hidden_line(line).is_some() {
+                hidden.push(block.fence_line + 1 + i);
+            }
+        }
+    }
+
+    doc.lines().enumerate().filter(|(i, _)| !hidden.contains(i)).map(|(i, line)| {
+        if fences.contains(&i) {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let fence = line.trim_start().chars().take_while(|c| *c == '`' || *c == '~').collect::<String>();
+            format!("{}{}noir", indent, fence)
+        }
+        else {
+            line.to_string()
+        }
+    }).collect::<Vec<_>>().join("\n")
+}
+
+/// collects the doc tests of all doc comments of the crate
+pub(crate) fn doc_tests(name: &str, items: &[Output]) -> Vec<DocTest> {
+    let mut res = Vec::new();
+    collect_tests(&mut res, name, items);
+
+    res
+}
+
+fn collect_tests(res: &mut Vec<DocTest>, module: &str, items: &[Output]) {
+    for item in items.iter() {
+        if item.r#type == Type::OuterComment {
+            res.extend(doc_comment_tests(&item.doc, module, &item.span.doc));
+            continue;
+        }
+
+        let path = format!("{}::{}", module, item.name);
+        res.extend(doc_comment_tests(&item.doc, &path, &item.span.doc));
+
+        if let Some(additional_doc) = item.information.get_additional_doc() {
+            res.extend(doc_comment_tests(&additional_doc, &path, &item.span.additional_doc));
+        }
+
+        match &item.information {
+            Info::Module { content, .. } => collect_tests(res, &path, content),
+            Info::Struct { implementations, .. } => {
+                for implementation in implementations.iter() {
+                    method_tests(res, &path, &implementation.functions);
+                }
+            }
+            Info::Trait { required_methods, provided_methods, .. } => {
+                method_tests(res, &path, required_methods);
+                method_tests(res, &path, provided_methods);
+            }
+            Info::Function { .. } | Info::Global { .. } | Info::TypeAlias { .. } | Info::Blanc => {}
+        }
+    }
+}
+
+fn method_tests(res: &mut Vec<DocTest>, path: &str, methods: &[Function]) {
+    for method in methods.iter() {
+        let method_path = format!("{}::{}", path, method.name);
+        res.extend(doc_comment_tests(&method.doc, &method_path, &method.span.doc));
+    }
+}
@@ -1,380 +1,708 @@
-use std::{fs::File, io::{BufReader, BufRead, Read}};
+use std::{fs::{self, File}, io::{BufReader, BufRead}, path::{Path, PathBuf}};
 
 use askama::Template;
-use noirc_frontend::{lexer::Lexer, token::{SpannedToken, Token, DocComments, Keyword}, hir::resolution::errors::Span};
+use noirc_frontend::{
+    lexer::Lexer,
+    parser::{parse_program, Item, ItemKind, ParsedModule},
+    token::{SpannedToken, Token, DocComments, FunctionAttribute, Keyword, SecondaryAttribute},
+    hir::resolution::errors::Span,
+    Distinctness, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident, LetStatement, NoirStruct, NoirTrait, NoirTypeAlias, PathKind, Pattern,
+    TraitItem, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData, Visibility,
+};
+
+use crate::{Function, Implementation, Info, Output};
+
+/// a file with a Noir code together with its tokens and parsed items
+pub(crate) struct SourceFile {
+    pub(crate) path: PathBuf,
+    pub(crate) text: String,
+    pub(crate) tokens: Vec<SpannedToken>,
+    pub(crate) module: ParsedModule,
+}
 
-use crate::{Function, Output};
+impl SourceFile {
+    /// returns the 1-based line of the byte offset
+    pub(crate) fn line(&self, offset: u32) -> usize {
+        line(&self.text, offset)
+    }
 
-pub(crate) fn get_module_content(tokens: &[Token], index: usize) -> Vec<Output> {
-    let mut content = Vec::new();
-    let mut i = index;
-    let mut brace_counter = 0;
+    /// lines the span covers
+    pub(crate) fn span(&self, span: Span) -> SourceSpan {
+        SourceSpan::new(self.path.clone(), self.line(span.start()), self.line(span.end().saturating_sub(1)))
+    }
 
-    
+    /// lines of the whole file
+    pub(crate) fn file_span(&self) -> SourceSpan {
+        SourceSpan::new(self.path.clone(), 1, self.text.lines().count().max(1))
+    }
 
-    loop {
-        match &tokens[i] {
-            Token::Semicolon => {
-                let filename = format!("input_files/{}.nr", tokens[i - 1]);
-                content = get_doc(&filename).unwrap();
-                break;
-            }
-            Token::LeftBrace => {
-                brace_counter += 1;
-                i += 1;
-                while brace_counter != 0 {
-                    match &tokens[i] {
-                        Token::LeftBrace => {
-                            brace_counter += 1;
-                            content.push(SpannedToken::new(tokens[i].clone(), Span::inclusive(0, 1)));
-                            i += 1;
-                        }
-                        Token::RightBrace => {
-                            brace_counter -= 1;
-                            content.push(SpannedToken::new(tokens[i].clone(), Span::inclusive(0, 1)));
-                            i += 1;
-                        }
-                        _ => {
-                            content.push(SpannedToken::new(tokens[i].clone(), Span::inclusive(0, 1)));
-                            i += 1;
-                        }
-                    }
-                }
-                break;
-            }
-            _ => {
-                i += 1;
-            }
+    /// lines the span of the item with the name covers, together with the lines of its doc comments
+    pub(crate) fn item_span(&self, span: Span, name: &Ident) -> SourceSpan {
+        SourceSpan { doc: self.doc_span(name, false), additional_doc: self.doc_span(name, true), ..self.span(span) }
+    }
+
+    /// lines the doc comments written before the item header of the name are at
+    pub(crate) fn doc_span(&self, name: &Ident, outer: bool) -> DocSpan {
+        self.comment_span(&header_doc_tokens(&self.tokens, name, outer))
+    }
+
+    /// lines the doc comment tokens are at, a block comment takes as many lines as its text
+    pub(crate) fn comment_span(&self, tokens: &[&SpannedToken]) -> DocSpan {
+        let lines = tokens.iter().flat_map(|token| {
+            let first = self.line(token.to_span().start());
+            let count = match token.token() {
+                Token::DocComment(DocComments::Single(doc) | DocComments::Block(doc) | DocComments::Outer(doc)) => doc.split('\n').count(),
+                _ => 1,
+            };
+
+            first..first + count
+        }).collect();
+
+        DocSpan { file: self.path.clone(), lines }
+    }
+}
+
+/// returns the 1-based line of the byte offset in the text
+pub(crate) fn line(text: &str, offset: u32) -> usize {
+    let end = (offset as usize).min(text.len());
+    text.as_bytes()[..end].iter().filter(|byte| **byte == b'\n').count() + 1
+}
+
+/// lines of a source file a doc comment is written at, one per line of the doc text
+/// attributes, comments and blank lines between the doc comments are skipped
+#[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
+pub(crate) struct DocSpan {
+    pub(crate) file: PathBuf,
+    pub(crate) lines: Vec<usize>,
+}
+
+impl DocSpan {
+    /// a doc text written from the line on, e.g. the note of an attribute
+    pub(crate) fn starting_at(file: &Path, line: usize) -> Self {
+        DocSpan { file: file.to_path_buf(), lines: vec![line] }
+    }
+
+    /// line of the line of the doc text with the index, the lines past the recorded ones follow the last of them
+    pub(crate) fn line(&self, index: usize) -> usize {
+        match self.lines.get(index) {
+            Some(line) => *line,
+            None => self.lines.last().map_or(index + 1, |last| last + 1 + index - self.lines.len()),
+        }
+    }
+}
+
+/// lines of a source file an item is defined at
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub(crate) struct SourceSpan {
+    pub(crate) file: PathBuf,
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    /// lines of the doc comment of the item
+    pub(crate) doc: DocSpan,
+    /// lines of the `//!` comments of a struct or a trait
+    pub(crate) additional_doc: DocSpan,
+}
+
+impl SourceSpan {
+    /// lines of an item without doc comments
+    pub(crate) fn new(file: PathBuf, start_line: usize, end_line: usize) -> Self {
+        let doc = DocSpan { file: file.clone(), lines: Vec::new() };
+
+        SourceSpan { file, start_line, end_line, additional_doc: doc.clone(), doc }
+    }
+
+    /// components of the file path relative to the crate source directory
+    /// files outside of it are identified by their name
+    pub(crate) fn relative_path(&self, src_root: &Path) -> Vec<String> {
+        let relative = match self.file.strip_prefix(src_root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => PathBuf::from(self.file.file_name().unwrap_or_default()),
         };
+
+        relative.iter().map(|component| component.to_string_lossy().to_string()).collect()
     }
 
-    let res = Output::to_output(content);
+    /// path of the source page of the file relative to the crate documentation directory
+    /// the source pages mirror the source tree under `src`
+    pub(crate) fn source_page(&self, src_root: &Path) -> Vec<String> {
+        let mut res = vec!["src".to_string()];
+        res.extend(self.relative_path(src_root));
+        if let Some(file_name) = res.last_mut() {
+            file_name.push_str(".html");
+        }
 
-    res
+        res
+    }
+
+    /// link to the line range on the source page relative to the crate documentation directory
+    pub(crate) fn link(&self, src_root: &Path) -> String {
+        format!("{}#L{}-L{}", self.source_page(src_root).join("/"), self.start_line, self.end_line)
+    }
 }
 
-pub(crate) fn skip_impl_block(tokens: &[Token], index: usize) -> usize {
-    let mut brace_counter = 0;
-    let mut i = index;
+/// location of a module in the source tree
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ModuleLocation {
+    /// file the module is written in
+    pub(crate) file: PathBuf,
+    /// directory the files of the declared submodules are looked up in
+    pub(crate) child_dir: PathBuf,
+}
 
-    while brace_counter != 1 {
-        match &tokens[i] {
-            Token::LeftBrace => {
-                i += 1;
-                brace_counter += 1;
-            }
-            _ => {
-                i += 1;
-            }
+impl ModuleLocation {
+    /// the crate root declares its submodules next to itself
+    pub(crate) fn crate_root(file: &Path) -> Self {
+        let child_dir = file.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        ModuleLocation { file: file.to_path_buf(), child_dir }
+    }
+
+    pub(crate) fn inline_module(&self, name: &str) -> Self {
+        ModuleLocation { file: self.file.clone(), child_dir: self.child_dir.join(name) }
+    }
+
+    /// resolves `mod name;` the same way the Noir compiler does:
+    /// the module is either `name.nr` or `name/mod.nr` in the directory of the declaring module,
+    /// and its own submodules are looked up in the `name` directory
+    fn declared_module(&self, name: &str, line: usize) -> Result<Self, Box<dyn std::error::Error>> {
+        let candidates = [
+            self.child_dir.join(format!("{}.nr", name)),
+            self.child_dir.join(name).join("mod.nr"),
+        ];
+
+        match candidates.iter().find(|candidate| candidate.is_file()) {
+            Some(file) => Ok(ModuleLocation { file: file.clone(), child_dir: self.child_dir.join(name) }),
+            None => Err(format!(
+                "file not found for module `{}` declared at {}:{}, expected {} or {}",
+                name,
+                self.file.display(),
+                line,
+                candidates[0].display(),
+                candidates[1].display(),
+            ).into()),
+        }
+    }
+}
+
+/// reads the content of a module declared with `mod name;` in the source file
+pub(crate) fn get_module_content(source: &SourceFile, location: &ModuleLocation, name: &Ident) -> Result<(Info, SourceSpan), Box<dyn std::error::Error>> {
+    let module = location.declared_module(&name.0.contents, source.line(name.span().start()))?;
+    let content = get_doc(&module.file)?;
+
+    let res = Output::from_file(&content, &module)?;
+    let impls = Implementation::from_items(&content.module.items, &content);
+
+    Ok((Info::Module { content: res, imports: imports(&content.module.items), impls }, content.file_span()))
+}
+
+/// an item brought into the scope of a module by a `use` statement
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub(crate) struct Import {
+    /// name of the item in the module
+    pub(crate) alias: String,
+    /// segments of the imported path, starting with `crate` or `dep` for such paths
+    pub(crate) path: Vec<String>,
+}
+
+/// collects the imports of the module items
+pub(crate) fn imports(items: &[Item]) -> Vec<Import> {
+    let mut res = Vec::new();
+
+    for item in items.iter() {
+        let ItemKind::Import(tree) = &item.kind else {
+            continue;
+        };
+
+        for import in tree.clone().desugar(None) {
+            let Some(last) = import.path.segments.last() else {
+                continue;
+            };
+
+            let mut path = match import.path.kind {
+                PathKind::Crate => vec!["crate".to_string()],
+                PathKind::Dep => vec!["dep".to_string()],
+                PathKind::Plain => Vec::new(),
+            };
+            path.extend(import.path.segments.iter().map(|segment| segment.0.contents.clone()));
+
+            let alias = import.alias.as_ref().unwrap_or(last).0.contents.clone();
+
+            res.push(Import { alias, path });
         }
     }
 
-    while brace_counter != 0 {
-        match &tokens[i] {
+    res
+}
+
+/// index of the first token starting at or after the offset
+fn token_index(tokens: &[SpannedToken], offset: u32) -> usize {
+    tokens.partition_point(|token| token.to_span().start() < offset)
+}
+
+/// span of a trait method from its header to the `;` of a declaration or the closing brace of its body
+/// the parser keeps no span of trait methods
+fn trait_method_span(tokens: &[SpannedToken], name: &Ident) -> Span {
+    let start = tokens.get(header_start(tokens, name)).map_or(name.span(), SpannedToken::to_span);
+    let mut depth = 0;
+
+    for token in tokens[token_index(tokens, name.span().start())..].iter() {
+        let end = match token.token() {
             Token::LeftBrace => {
-                i += 1;
-                brace_counter += 1;
+                depth += 1;
+                false
             }
             Token::RightBrace => {
-                i += 1;
-                brace_counter -= 1;
-            }
-            _ => {
-                i += 1;
+                depth -= 1;
+                depth <= 0
             }
+            Token::Semicolon => depth == 0,
+            _ => false,
+        };
+        if end {
+            return name.span().merge(start).merge(token.to_span());
         }
     }
 
-    i - index - 1
+    name.span()
 }
 
-pub(crate) fn fn_signature(tokens: &[Token], index: usize) -> String {
-    let mut res = String::new();
-    let mut i = index;
-    loop {
-        match &tokens[i] {
-            Token::LeftBrace | Token::Semicolon => {
-                break;
+/// index of the first token of the item header the name belongs to
+/// the header consists of attributes, visibility, modifiers and the item keyword
+fn header_start(tokens: &[SpannedToken], name: &Ident) -> usize {
+    let mut i = token_index(tokens, name.span().start());
+
+    while i > 0 {
+        match tokens[i - 1].token() {
+            Token::Keyword(_) | Token::LeftParen | Token::RightParen | Token::Attribute(_) => {
+                i -= 1;
             }
             _ => {
-                res.push_str(&tokens[i].to_string());
-                res.push_str(" ");
-                i += 1;
+                break;
             }
-        };
+        }
     }
-    res
+
+    i
 }
 
-pub(crate) fn struct_signature(tokens: &[Token], index: usize) -> String {
-    let mut res = String::new();
-    let mut i = index;
-    let mut is_private = true;
+/// visibility of an item written in its header
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub(crate) enum ItemVisibility {
+    /// `pub`
+    Public,
+    /// `pub(crate)`
+    Crate,
+    Private,
+}
 
-    loop {
-        match &tokens[i + 1] {
-            Token::LeftBrace => {
-                res.push_str("{");
-                res.push_str("\n");
-                loop {
-                    match tokens[i + 1] {
-                        Token::RightBrace => {
-                            if is_private {
-                                res.push_str("/* private fields */");
-                            }
-                            res.push_str("\n");
-                            res.push_str("}");
-                            break;
-                        }
-                        Token::Keyword(Keyword::Pub) => {
-                            is_private = false;
-                            loop {
-                                match tokens[i + 1] {
-                                    Token::Comma => {
-                                        if tokens[i + 2] == Token::RightBrace {
-                                            res.push_str(",");
-                                        }
-                                        else {
-                                            res.push_str(",\n");
-                                        }
-                                        i += 1;
-                                        break;
-                                    }
-                                    Token::RightBrace => {
-                                        break;
-                                    }
-                                    _ => {
-                                        res.push_str(&tokens[i + 1].to_string());
-                                        res.push_str(" ");
-                                        i += 1;
-                                    }
-                                }
-                            }
-                        }
-                        _ => { i += 1; }
-                    }
-                }
-                break;
-            }
-            _ => {
-                res.push_str(&tokens[i + 1].to_string());
-                res.push_str(" ");
-                i += 1;
-            }
-        };
+impl ItemVisibility {
+    /// the visibility as it is written before the item
+    pub(crate) fn keyword(&self) -> &'static str {
+        match self {
+            ItemVisibility::Public => "pub ",
+            ItemVisibility::Crate => "pub(crate) ",
+            ItemVisibility::Private => "",
+        }
     }
 
-    res
+    /// the badge shown next to the items that are not public
+    pub(crate) fn badge(&self) -> &'static str {
+        match self {
+            ItemVisibility::Public => "",
+            ItemVisibility::Crate => "pub(crate)",
+            ItemVisibility::Private => "private",
+        }
+    }
 }
 
-pub(crate) fn trait_info(tokens: &[Token], index: usize) -> (String, Vec<Function>, Vec<Function>) {
-    let mut sign = String::new();
-    let mut required_methods = Vec::new();
-    let mut provided_methods = Vec::new();
-    let mut i = index;
-    let mut brace_counter;
+/// reads the visibility from the item header of the name
+pub(crate) fn visibility(tokens: &[SpannedToken], name: &Ident) -> ItemVisibility {
+    let end = token_index(tokens, name.span().start());
+    let header = &tokens[header_start(tokens, name)..end];
 
-    loop {
-        match &tokens[i + 1] {
-            Token::LeftBrace => {
-                sign.push_str("{");
-                sign.push_str("\n");
-                loop {
-                    match tokens[i + 1] {
-                        Token::RightBrace => {
-                            sign.push_str("}");
-                            break;
-                        }
-                        Token::Keyword(Keyword::Fn) => {
-                            let name = match &tokens[i + 2] {
-                                Token::Ident(idn) => {
-                                    idn.clone()
-                                }
-                                _ => {break;}
-                            };
-                            let doc = doc(&tokens, i + 1);
-                            let fn_sign = fn_signature(&tokens, i + 1);
-
-                            loop {
-                                match tokens[i + 1] {
-                                    Token::Semicolon => {
-                                        required_methods.push(Function { name, doc, signature: fn_sign, is_method: true });
-                                        sign.push_str(";");
-                                        sign.push_str("\n");
-                                        break;
-                                    }
-                                    Token::LeftBrace => {
-                                        provided_methods.push(Function { name, doc, signature: fn_sign, is_method: true });
-                                        brace_counter = 1;
-                                        sign.push_str("{ ... }");
-                                        sign.push_str("\n");
-                                        while brace_counter != 0 {
-                                            i += 1;
-                                            match tokens[i + 1] {
-                                                Token::LeftBrace => {
-                                                    brace_counter +=1;
-                                                }
-                                                Token::RightBrace => {
-                                                    brace_counter -=1;
-                                                }
-                                                _ => {}
-                                            }
-                                        }
-                                        i +=1;
-                                        break;
-                                    }
-                                    _ => {
-                                        sign.push_str(&tokens[i + 1].to_string());
-                                        sign.push_str(" ");
-                                        i += 1;
-                                    }
-                                }
-                            }
-                        }
-                        _ => { i += 1; }
-                    }
+    match header.iter().position(|token| token.token() == &Token::Keyword(Keyword::Pub)) {
+        Some(i) if header.get(i + 1).map(|token| token.token()) == Some(&Token::LeftParen) => ItemVisibility::Crate,
+        Some(_) => ItemVisibility::Public,
+        None => ItemVisibility::Private,
+    }
+}
+
+/// collects the doc comment tokens written right before the item header
+/// `outer` selects `//!` comments instead of `///` and `/** */` ones
+fn header_doc_tokens<'a>(tokens: &'a [SpannedToken], name: &Ident, outer: bool) -> Vec<&'a SpannedToken> {
+    let mut res = Vec::new();
+    let mut i = header_start(tokens, name);
+
+    while i > 0 {
+        match tokens[i - 1].token() {
+            Token::DocComment(DocComments::Single(_)) |
+            Token::DocComment(DocComments::Block(_)) => {
+                if !outer {
+                    res.push(&tokens[i - 1]);
+                }
+            }
+            Token::DocComment(DocComments::Outer(_)) => {
+                if outer {
+                    res.push(&tokens[i - 1]);
                 }
-                break;
             }
+            Token::LineComment(..) | Token::BlockComment(..) | Token::Attribute(_) => {}
             _ => {
-                sign.push_str(&tokens[i + 1].to_string());
-                sign.push_str(" ");
-                i += 1;
+                break;
             }
-        };
+        }
+        i -= 1;
     }
 
-    (sign, required_methods, provided_methods)
+    res.reverse();
+    res
+}
+
+/// text of the doc comment tokens, one comment per line
+pub(crate) fn doc_text(tokens: &[&SpannedToken]) -> String {
+    tokens.iter().filter_map(|token| match token.token() {
+        Token::DocComment(DocComments::Single(doc) | DocComments::Block(doc) | DocComments::Outer(doc)) => Some(doc.as_str()),
+        _ => None,
+    }).collect::<Vec<_>>().join("\n")
+}
+
+fn header_doc(tokens: &[SpannedToken], name: &Ident, outer: bool) -> String {
+    doc_text(&header_doc_tokens(tokens, name, outer))
+}
+
+pub(crate) fn doc(tokens: &[SpannedToken], name: &Ident) -> String {
+    header_doc(tokens, name, false)
+}
+
+pub(crate) fn additional_doc(tokens: &[SpannedToken], name: &Ident) -> String {
+    header_doc(tokens, name, true)
 }
 
-pub(crate) fn additional_doc(tokens: &[Token], index: usize) -> String {
-    let res = match &tokens[index - 1] {
-        Token::DocComment(DocComments::Outer(dc)) => {
-            let mut res = dc.to_string();
-            let mut doc_end = true;
-            let mut iter = 2;
-            while doc_end && ((index as i32) - (iter as i32)) >= 0 {
-                match &tokens[index - iter] {
-                    Token::DocComment(DocComments::Outer(doc)) => {
-                        res.insert_str(0, &doc.to_string());
-                        iter += 1;
+/// collects the runs of `//!` comments between `start` and `end` that are not a part of any item
+/// returns the tokens of every run
+pub(crate) fn outer_doc<'a>(tokens: &'a [SpannedToken], items: &[Span], start: u32, end: u32) -> Vec<Vec<&'a SpannedToken>> {
+    let mut res: Vec<Vec<&SpannedToken>> = Vec::new();
+    let mut previous_was_outer = false;
+
+    for token in tokens[token_index(tokens, start)..token_index(tokens, end)].iter() {
+        let offset = token.to_span().start();
+        if items.iter().any(|item| item.start() <= offset && offset < item.end()) {
+            previous_was_outer = false;
+            continue;
+        }
+
+        match token.token() {
+            Token::DocComment(DocComments::Outer(_)) => {
+                match res.last_mut() {
+                    Some(run) if previous_was_outer => {
+                        run.push(token);
                     }
                     _ => {
-                        doc_end = false;
+                        res.push(vec![token]);
                     }
                 }
+                previous_was_outer = true;
             }
-            res
-        }
-        _ => {
-            let mut res = String::new();
-        
-            let mut doc_find = true;
-            let mut iter = 2;
-            while doc_find && ((index as i32) - (iter as i32)) >= 0 {
-                match &tokens[index - iter] {
-                    Token::DocComment(DocComments::Outer(doc)) => {
-                        res.insert_str(0, &doc.to_string());
-                        iter += 1;
-                    }
-                    Token::Keyword(Keyword::Fn) | Token::Keyword(Keyword::Mod) |
-                    Token::Keyword(Keyword::Struct) | Token::Keyword(Keyword::Trait) |
-                    Token::Keyword(Keyword::Impl) => {
-                        doc_find = false;
-                    }
-                    _ => { iter += 1; }
-                }
+            _ => {
+                previous_was_outer = false;
             }
-            res
         }
-    };
+    }
+
     res
 }
 
-pub(crate) fn doc(tokens: &[Token], index: usize) -> String {
-    if index == 0 {
+pub(crate) fn generics(generics: &[Ident]) -> String {
+    if generics.is_empty() {
         return String::new();
     }
-    let res = match &tokens[index - 1] {
-        Token::DocComment(DocComments::Single(dc)) | 
-        Token::DocComment(DocComments::Block(dc)) => {
-            let mut res = dc.to_string();
-            let mut doc_end = true;
-            let mut iter = 2;
-            while doc_end && ((index as i32) - (iter as i32)) >= 0 {
-                match &tokens[index - iter] {
-                    Token::DocComment(DocComments::Single(doc)) | 
-                    Token::DocComment(DocComments::Block(doc)) => {
-                        res.insert_str(0, &doc.to_string());
-                        iter += 1;
-                    }
-                    _ => {
-                        doc_end = false;
-                    }
-                }
-            }
-            res
+
+    let generics = generics.iter().map(|generic| generic.to_string()).collect::<Vec<_>>();
+    format!("<{}>", generics.join(", "))
+}
+
+pub(crate) fn where_clause(constraints: &[UnresolvedTraitConstraint]) -> String {
+    if constraints.is_empty() {
+        return String::new();
+    }
+
+    let constraints = constraints.iter().map(|constraint| constraint.to_string()).collect::<Vec<_>>();
+    format!(" where {}", constraints.join(", "))
+}
+
+fn return_type(return_type: &FunctionReturnType) -> String {
+    match return_type {
+        FunctionReturnType::Default(_) => String::new(),
+        FunctionReturnType::Ty(typ) => format!(" -> {}", typ),
+    }
+}
+
+/// path of the struct or trait the type refers to as written, without its generics
+pub(crate) fn type_path(typ: &UnresolvedType) -> Option<String> {
+    match &typ.typ {
+        UnresolvedTypeData::Named(path, ..) => Some(path.to_string()),
+        _ => None,
+    }
+}
+
+/// the note of the `#[deprecated]` attribute among the attributes of an item, empty if it has none
+pub(crate) fn deprecation(attributes: &[SecondaryAttribute]) -> Option<String> {
+    attributes.iter().find_map(|attribute| match attribute {
+        SecondaryAttribute::Deprecated(note) => Some(note.clone().unwrap_or_default()),
+        _ => None,
+    })
+}
+
+/// the attributes of an item as written, e.g. `#[event]` or `#[aztec(private)]`
+/// the deprecation is shown as a notice rather than a badge
+pub(crate) fn secondary_attributes(attributes: &[SecondaryAttribute]) -> Vec<String> {
+    attributes.iter()
+        .filter(|attribute| !matches!(attribute, SecondaryAttribute::Deprecated(_)))
+        .map(|attribute| attribute.to_string())
+        .collect()
+}
+
+/// attributes and modifiers of a function
+#[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
+pub(crate) struct FunctionAttributes {
+    /// the attributes as written, e.g. `#[test(should_fail)]` or `#[oracle(get_notes)]`
+    pub(crate) attributes: Vec<String>,
+    /// e.g. `unconstrained` or `distinct return`, the pinned Noir version has no `comptime` functions
+    pub(crate) modifiers: Vec<String>,
+    pub(crate) is_test: bool,
+    /// the note of a `#[deprecated]` attribute, empty if it has none
+    pub(crate) deprecated: Option<String>,
+}
+
+impl FunctionAttributes {
+    pub(crate) fn new(def: &FunctionDefinition) -> Self {
+        let mut attributes = Vec::new();
+        if let Some(attribute) = &def.attributes.function {
+            attributes.push(attribute.to_string());
+        }
+        attributes.extend(secondary_attributes(&def.attributes.secondary));
+
+        let modifiers = [
+            (def.is_unconstrained, "unconstrained"),
+            (def.is_open, "open"),
+            (def.is_internal, "internal"),
+            (def.return_distinctness == Distinctness::Distinct, "distinct return"),
+            (def.return_visibility == Visibility::Public, "pub return"),
+        ];
+        let modifiers = modifiers.iter().filter(|(is_set, _)| *is_set).map(|(_, modifier)| modifier.to_string()).collect();
+
+        let is_test = matches!(def.attributes.function, Some(FunctionAttribute::Test(_)));
+
+        FunctionAttributes { attributes, modifiers, is_test, deprecated: deprecation(&def.attributes.secondary) }
+    }
+
+    /// badges of the function, the modifiers go first
+    pub(crate) fn badges(&self) -> Vec<String> {
+        self.modifiers.iter().chain(self.attributes.iter()).cloned().collect()
+    }
+}
+
+pub(crate) fn fn_signature(def: &FunctionDefinition) -> String {
+    let mut res = String::new();
+
+    match def.visibility {
+        FunctionVisibility::Public => res.push_str("pub "),
+        FunctionVisibility::PublicCrate => res.push_str("pub(crate) "),
+        FunctionVisibility::Private => {}
+    }
+    if def.is_unconstrained {
+        res.push_str("unconstrained ");
+    }
+
+    let parameters = def.parameters.iter().map(|param| {
+        match param.visibility {
+            Visibility::Public => format!("{}: pub {}", param.pattern, param.typ),
+            _ => format!("{}: {}", param.pattern, param.typ),
+        }
+    }).collect::<Vec<_>>();
+
+    res.push_str(&format!("fn {}{}({})", def.name, generics(&def.generics), parameters.join(", ")));
+
+    if let FunctionReturnType::Ty(typ) = &def.return_type {
+        res.push_str(" -> ");
+        if def.return_distinctness == Distinctness::Distinct {
+            res.push_str("distinct ");
         }
-        _ => {
-            let mut res = String::new();
-        
-            let mut doc_find = true;
-            let mut iter = 2;
-            while doc_find && ((index as i32) - (iter as i32)) >= 0 {
-                match &tokens[index - iter] {
-                    Token::DocComment(DocComments::Single(doc)) | 
-                    Token::DocComment(DocComments::Block(doc)) => {
-                        res.insert_str(0, &doc.to_string());
-                        iter += 1;
+        if def.return_visibility == Visibility::Public {
+            res.push_str("pub ");
+        }
+        res.push_str(&typ.to_string());
+    }
+
+    res.push_str(&where_clause(&def.where_clause));
+
+    res
+}
+
+/// name of a global, globals declared with a tuple pattern have no single name
+pub(crate) fn pattern_name(pattern: &Pattern) -> Option<Ident> {
+    match pattern {
+        Pattern::Identifier(name) => Some(name.clone()),
+        Pattern::Mutable(pattern, ..) => pattern_name(pattern),
+        _ => None,
+    }
+}
+
+/// the type of a global, empty if it is not written
+pub(crate) fn global_type(global: &LetStatement) -> String {
+    match global.r#type.typ {
+        UnresolvedTypeData::Unspecified => String::new(),
+        _ => global.r#type.to_string(),
+    }
+}
+
+pub(crate) fn global_signature(tokens: &[SpannedToken], name: &Ident, global: &LetStatement) -> String {
+    let typ = global_type(global);
+    let typ = if typ.is_empty() { typ } else { format!(": {}", typ) };
+
+    format!("{}global {}{} = {};", visibility(tokens, name).keyword(), name, typ, global.expression)
+}
+
+pub(crate) fn type_alias_signature(tokens: &[SpannedToken], alias: &NoirTypeAlias) -> String {
+    format!("{}type {}{} = {};", visibility(tokens, &alias.name).keyword(), alias.name, generics(&alias.generics), alias.typ)
+}
+
+/// the signature of a struct with all its fields
+pub(crate) fn struct_signature(structure: &NoirStruct, fields: &[Field]) -> String {
+    fields_signature(&format!("struct {}{}", structure.name, generics(&structure.generics)), fields, false)
+}
+
+/// the signature of a struct with the header and the fields, `private_fields` marks hidden fields with a comment
+pub(crate) fn fields_signature(header: &str, fields: &[Field], private_fields: bool) -> String {
+    let mut res = format!("{} {{\n", header);
+
+    for field in fields.iter() {
+        res.push_str(&format!("    {}{}: {},\n", field.visibility.keyword(), field.name, field.r#type));
+    }
+
+    if private_fields {
+        res.push_str("    /* private fields */\n");
+    }
+    res.push('}');
+
+    res
+}
+
+/// a field of a struct
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) name: String,
+    pub(crate) r#type: String,
+    pub(crate) doc: String,
+    pub(crate) visibility: ItemVisibility,
+    pub(crate) span: SourceSpan,
+}
+
+impl Field {
+    /// id of the field section on the struct page
+    pub(crate) fn anchor(&self) -> String {
+        field_anchor(&self.name)
+    }
+}
+
+pub(crate) fn field_anchor(name: &str) -> String {
+    format!("structfield.{}", name)
+}
+
+pub(crate) fn method_anchor(name: &str) -> String {
+    format!("method.{}", name)
+}
+
+pub(crate) fn struct_fields(source: &SourceFile, structure: &NoirStruct) -> Vec<Field> {
+    let tokens = &source.tokens;
+
+    structure.fields.iter().map(|(name, typ)| {
+        let span = match typ.span {
+            Some(typ_span) => name.span().merge(typ_span),
+            None => name.span(),
+        };
+
+        Field { name: name.to_string(), r#type: typ.to_string(), doc: doc(tokens, name), visibility: visibility(tokens, name), span: source.item_span(span, name) }
+    }).collect()
+}
+
+pub(crate) fn trait_info(source: &SourceFile, r#trait: &NoirTrait) -> (String, Vec<Function>, Vec<Function>) {
+    let tokens = &source.tokens;
+    // trait methods are as visible as their trait
+    let trait_visibility = visibility(tokens, &r#trait.name);
+    let mut sign = format!("trait {}{}{} {{\n", r#trait.name, generics(&r#trait.generics), where_clause(&r#trait.where_clause));
+    let mut required_methods = Vec::new();
+    let mut provided_methods = Vec::new();
+
+    for item in r#trait.items.iter() {
+        match item {
+            TraitItem::Function { name, generics: fn_generics, parameters, return_type: ret, where_clause: constraints, body } => {
+                let parameters = parameters.iter().map(|(name, typ)| format!("{}: {}", name, typ)).collect::<Vec<_>>();
+                let fn_sign = format!("fn {}{}({}){}{}", name, generics(fn_generics), parameters.join(", "), return_type(ret), where_clause(constraints));
+                let function = Function { name: name.to_string(), doc: doc(tokens, name), signature: fn_sign.clone(), is_method: true, visibility: trait_visibility, attributes: FunctionAttributes::default(), span: source.item_span(trait_method_span(tokens, name), name), source_link: String::new(), assets: String::new(), anchor_index: 0 };
+
+                match body {
+                    Some(_) => {
+                        sign.push_str(&format!("    {} {{ ... }}\n", fn_sign));
+                        provided_methods.push(function);
                     }
-                    Token::Keyword(Keyword::Fn) | Token::Keyword(Keyword::Mod) |
-                    Token::Keyword(Keyword::Struct) | Token::Keyword(Keyword::Trait) |
-                    Token::Keyword(Keyword::Impl) => {
-                        doc_find = false;
+                    None => {
+                        sign.push_str(&format!("    {};\n", fn_sign));
+                        required_methods.push(function);
                     }
-                    _ => { iter += 1; }
                 }
             }
-            res
+            TraitItem::Constant { name, typ, .. } => {
+                sign.push_str(&format!("    let {}: {};\n", name, typ));
+            }
+            TraitItem::Type { name } => {
+                sign.push_str(&format!("    type {};\n", name));
+            }
         }
-    };
-    res
+    }
+    sign.push('}');
+
+    (sign, required_methods, provided_methods)
 }
 
-pub(crate) fn outer_doc(tokens: &[Token], index: usize) -> (String, usize) {
-    let mut i = index;
-    let mut res = tokens[i].to_string();
-    let mut doc_find = true;
-    while doc_find {
-        match &tokens[i + 1] {
-            Token::DocComment(DocComments::Outer(doc)) => {
-                res.push_str(doc);
-                i += 1;
-            }
-            _ => { doc_find = false; }
+/// splits a file with a Noir code into tokens, the comments are kept
+pub(crate) fn lex(input_file: &Path, text: &str) -> Result<Vec<SpannedToken>, Box<dyn std::error::Error>> {
+    let mut tokens = Vec::new();
+    for token in Lexer::new(text).skip_comments(false) {
+        match token {
+            Ok(token) => tokens.push(token),
+            Err(err) => return Err(format!("failed to lex {}: {}", input_file.display(), err).into()),
         }
     }
 
-    (res, i)
+    Ok(tokens)
 }
 
-pub(crate) fn get_doc(input_file: &str) -> Result<Vec<SpannedToken>, Box<dyn std::error::Error>> {
-    let mut file = File::open(input_file)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+/// reads and parses a file with a Noir code
+/// parser errors are reported as warnings, the items the parser recovered are still documented
+pub(crate) fn get_doc(input_file: &Path) -> Result<SourceFile, Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(input_file)
+        .map_err(|err| format!("failed to open {}: {}", input_file.display(), err))?;
+
+    let tokens = lex(input_file, &text)?;
 
-    let mut lexer = Lexer::new(&contents);
-    lexer = lexer.skip_comments(false);
+    let (module, errors) = parse_program(&text);
 
-    let token = lexer.into_iter().map(|a| a.unwrap()).collect::<Vec<_>>();
+    let source = SourceFile { path: input_file.to_path_buf(), text, tokens, module };
+
+    for err in errors.iter() {
+        eprintln!("warning: {}:{}: {}", input_file.display(), source.line(err.span().start()), err);
+    }
 
-    Ok(token)
+    Ok(source)
 }
 
 #[derive(Template)]
 #[template(path = "code_template.html")]
 pub(crate) struct Code {
+    pub(crate) filename: String,
     pub(crate) codelines: Vec<CodeLine>,
+    pub(crate) assets: String,
 }
 
 #[derive(Debug)]
@@ -383,15 +711,20 @@ pub(crate) struct CodeLine {
     text: String,
 }
 
-pub(crate) fn get_text(input_file: &str) -> Result<Vec<CodeLine>, Box<dyn std::error::Error>> {
+impl CodeLine {
+    /// id of the line element, source links point at it with `#L<number>`
+    pub(crate) fn anchor(&self) -> String {
+        format!("L{}", self.number)
+    }
+}
+
+pub(crate) fn get_text(input_file: &Path) -> Result<Vec<CodeLine>, Box<dyn std::error::Error>> {
     let file = File::open(input_file)?;
     let reader = BufReader::new(file);
     let mut code = Vec::new();
-    let mut i = 0;
 
-    for line in reader.lines() {
-        i += 1;
-        code.push(CodeLine{ number: i, text: line? });
+    for (i, line) in reader.lines().enumerate() {
+        code.push(CodeLine{ number: i as u32 + 1, text: line? });
     }
 
     Ok(code)
@@ -0,0 +1,284 @@
+use std::{fs::{self, File}, io::Write, path::Path};
+
+use serde::Serialize;
+
+use crate::{Field, Function, Implementation, Info, ItemVisibility, Output, SourceSpan, Type};
+
+/// version of the json format
+/// increased on every change of the schema that is not backwards compatible
+pub(crate) const FORMAT_VERSION: u32 = 1;
+
+/// root object of the json output
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonCrate {
+    pub(crate) format_version: u32,
+    pub(crate) name: String,
+    pub(crate) root: JsonItem,
+}
+
+/// a documented item, the `kind` field tells which one
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum JsonItem {
+    Module {
+        name: String,
+        docs: String,
+        span: JsonSpan,
+        items: Vec<JsonItem>,
+    },
+    /// an Aztec contract, its items are listed like the items of a module
+    Contract {
+        name: String,
+        docs: String,
+        span: JsonSpan,
+        items: Vec<JsonItem>,
+    },
+    Function {
+        name: String,
+        docs: String,
+        signature: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        attributes: Vec<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        modifiers: Vec<String>,
+        /// the note of a `#[deprecated]` attribute, left out if the item is not deprecated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        deprecated: Option<String>,
+        span: JsonSpan,
+    },
+    Struct {
+        name: String,
+        docs: String,
+        additional_docs: String,
+        signature: String,
+        fields: Vec<JsonField>,
+        impls: Vec<JsonImpl>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        attributes: Vec<String>,
+        /// the note of a `#[deprecated]` attribute, left out if the item is not deprecated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        deprecated: Option<String>,
+        span: JsonSpan,
+    },
+    Trait {
+        name: String,
+        docs: String,
+        additional_docs: String,
+        signature: String,
+        required_methods: Vec<JsonFunction>,
+        provided_methods: Vec<JsonFunction>,
+        implementors: Vec<JsonImpl>,
+        span: JsonSpan,
+    },
+    Global {
+        name: String,
+        docs: String,
+        signature: String,
+        /// `null` if the type is not written
+        r#type: Option<String>,
+        value: String,
+        span: JsonSpan,
+    },
+    TypeAlias {
+        name: String,
+        docs: String,
+        signature: String,
+        /// the aliased type
+        r#type: String,
+        span: JsonSpan,
+    },
+}
+
+/// a method of an impl block or a trait
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonFunction {
+    pub(crate) name: String,
+    pub(crate) docs: String,
+    pub(crate) signature: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) attributes: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) modifiers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) deprecated: Option<String>,
+    pub(crate) span: JsonSpan,
+}
+
+/// a field of a struct
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonField {
+    pub(crate) name: String,
+    pub(crate) r#type: String,
+    /// `pub`, `pub(crate)` or `private`
+    pub(crate) visibility: &'static str,
+    pub(crate) docs: String,
+    pub(crate) span: JsonSpan,
+}
+
+/// an impl block
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonImpl {
+    pub(crate) signature: String,
+    /// path of the implemented trait as written, `null` for inherent impls
+    pub(crate) r#trait: Option<String>,
+    /// path of the implemented type, from the crate root if it is a struct of the crate
+    pub(crate) r#type: String,
+    pub(crate) methods: Vec<JsonFunction>,
+    pub(crate) span: JsonSpan,
+}
+
+/// lines of a source file, the file path is relative to the crate source directory
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonSpan {
+    pub(crate) file: String,
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+}
+
+impl JsonCrate {
+    pub(crate) fn new(name: String, items: &[Output], span: &SourceSpan, src_root: &Path) -> Self {
+        let root = JsonItem::module(name.clone(), items, span, src_root);
+
+        JsonCrate { format_version: FORMAT_VERSION, name, root }
+    }
+}
+
+impl JsonItem {
+    fn module(name: String, items: &[Output], span: &SourceSpan, src_root: &Path) -> Self {
+        let docs = items.iter()
+            .filter(|item| item.r#type == Type::OuterComment)
+            .map(|item| item.doc.clone())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let items = items.iter().filter_map(|item| JsonItem::from_output(item, src_root)).collect();
+
+        JsonItem::Module { name, docs, span: JsonSpan::new(span, src_root), items }
+    }
+
+    fn from_output(output: &Output, src_root: &Path) -> Option<Self> {
+        let name = output.name.clone();
+        let docs = output.doc.clone();
+        let span = JsonSpan::new(&output.span, src_root);
+
+        let item = match &output.information {
+            Info::Function { signature, attributes } => {
+                JsonItem::Function {
+                    name,
+                    docs,
+                    signature: signature.clone(),
+                    attributes: attributes.attributes.clone(),
+                    modifiers: attributes.modifiers.clone(),
+                    deprecated: attributes.deprecated.clone(),
+                    span,
+                }
+            }
+            Info::Module { content, .. } => {
+                match JsonItem::module(name, content, &output.span, src_root) {
+                    JsonItem::Module { name, docs, span, items } if output.r#type == Type::Contract => JsonItem::Contract { name, docs, span, items },
+                    module => module,
+                }
+            }
+            Info::Struct { signature, additional_doc, fields, implementations, attributes, deprecated } => {
+                JsonItem::Struct {
+                    name,
+                    docs,
+                    additional_docs: additional_doc.clone(),
+                    signature: signature.clone(),
+                    fields: JsonField::list(fields, src_root),
+                    impls: JsonImpl::list(implementations, src_root),
+                    attributes: attributes.clone(),
+                    deprecated: deprecated.clone(),
+                    span,
+                }
+            }
+            Info::Trait { signature, additional_doc, required_methods, provided_methods, implementations } => {
+                JsonItem::Trait {
+                    name,
+                    docs,
+                    additional_docs: additional_doc.clone(),
+                    signature: signature.clone(),
+                    required_methods: JsonFunction::list(required_methods, src_root),
+                    provided_methods: JsonFunction::list(provided_methods, src_root),
+                    implementors: JsonImpl::list(implementations, src_root),
+                    span,
+                }
+            }
+            Info::Global { signature, r#type, value } => {
+                JsonItem::Global {
+                    name,
+                    docs,
+                    signature: signature.clone(),
+                    r#type: (!r#type.is_empty()).then(|| r#type.clone()),
+                    value: value.clone(),
+                    span,
+                }
+            }
+            Info::TypeAlias { signature, aliased_type } => {
+                JsonItem::TypeAlias { name, docs, signature: signature.clone(), r#type: aliased_type.clone(), span }
+            }
+            Info::Blanc => {
+                return None;
+            }
+        };
+
+        Some(item)
+    }
+}
+
+impl JsonFunction {
+    fn list(functions: &[Function], src_root: &Path) -> Vec<Self> {
+        functions.iter().map(|function| JsonFunction {
+            name: function.name.clone(),
+            docs: function.doc.clone(),
+            signature: function.signature.clone(),
+            attributes: function.attributes.attributes.clone(),
+            modifiers: function.attributes.modifiers.clone(),
+            deprecated: function.attributes.deprecated.clone(),
+            span: JsonSpan::new(&function.span, src_root),
+        }).collect()
+    }
+}
+
+impl JsonField {
+    fn list(fields: &[Field], src_root: &Path) -> Vec<Self> {
+        fields.iter().map(|field| JsonField {
+            name: field.name.clone(),
+            r#type: field.r#type.clone(),
+            visibility: match field.visibility {
+                ItemVisibility::Public => "pub",
+                ItemVisibility::Crate => "pub(crate)",
+                ItemVisibility::Private => "private",
+            },
+            docs: field.doc.clone(),
+            span: JsonSpan::new(&field.span, src_root),
+        }).collect()
+    }
+}
+
+impl JsonImpl {
+    fn list(implementations: &[Implementation], src_root: &Path) -> Vec<Self> {
+        implementations.iter().map(|implementation| JsonImpl {
+            signature: implementation.signature.clone(),
+            r#trait: implementation.trait_name.clone(),
+            r#type: if implementation.self_path.is_empty() { implementation.self_type.clone() } else { implementation.self_path.join("::") },
+            methods: JsonFunction::list(&implementation.functions, src_root),
+            span: JsonSpan::new(&implementation.span, src_root),
+        }).collect()
+    }
+}
+
+impl JsonSpan {
+    fn new(span: &SourceSpan, src_root: &Path) -> Self {
+        JsonSpan { file: span.relative_path(src_root).join("/"), start_line: span.start_line, end_line: span.end_line }
+    }
+}
+
+/// writes the crate documentation to `<output dir>/<crate name>.json`
+pub(crate) fn generate_json(json: &JsonCrate, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(output_dir)?;
+
+    let mut file = File::create(output_dir.join(format!("{}.json", json.name)))?;
+    file.write_all(serde_json::to_string_pretty(json)?.as_bytes())?;
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::{collections::{HashMap, HashSet}, fmt, fs, path::{Component, Path, PathBuf}};
+
+use crate::SEARCH_INDEX;
+
+/// a link of a generated page pointing at a file or an anchor that does not exist
+#[derive(Debug, PartialEq)]
+pub(crate) struct DanglingLink {
+    pub(crate) page: PathBuf,
+    pub(crate) link: String,
+}
+
+impl fmt::Display for DanglingLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: broken link `{}`", self.page.display(), self.link)
+    }
+}
+
+/// checks the links and anchors of all html pages and search indexes in the output directory
+/// links to other sites are not checked
+pub(crate) fn check_links(output_dir: &Path) -> Result<Vec<DanglingLink>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    collect_files(output_dir, &mut files)?;
+    files.sort();
+
+    let mut anchors = HashMap::new();
+    let mut res = Vec::new();
+
+    for file in files.iter() {
+        let links = match file.file_name().and_then(|name| name.to_str()) {
+            Some(name) if name.ends_with(".html") => {
+                let text = fs::read_to_string(file)?;
+                attribute_values(&text, "href").into_iter().chain(attribute_values(&text, "src")).collect()
+            }
+            Some(SEARCH_INDEX) => search_index_links(&fs::read_to_string(file)?)?,
+            _ => continue,
+        };
+
+        for link in links.into_iter() {
+            if !is_internal(&link) {
+                continue;
+            }
+            if !link_exists(file, &link, &mut anchors)? {
+                res.push(DanglingLink { page: file.clone(), link });
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        }
+        else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// the values of all `name="..."` attributes of the page
+fn attribute_values(text: &str, name: &str) -> Vec<String> {
+    let pattern = format!(" {}=\"", name);
+    let mut res = Vec::new();
+
+    for (start, _) in text.match_indices(&pattern) {
+        let value = &text[start + pattern.len()..];
+        if let Some(end) = value.find('"') {
+            res.push(unescape(&value[..end]));
+        }
+    }
+
+    res
+}
+
+fn unescape(value: &str) -> String {
+    value.replace("&#x2f;", "/").replace("&#x2F;", "/").replace("&#x27;", "'").replace("&#39;", "'")
+        .replace("&quot;", "\"").replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
+}
+
+/// the links of the entries of a `search-index.js` file
+fn search_index_links(text: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let json = text.trim().trim_start_matches("window.searchIndex =").trim_end_matches(';');
+    let entries: Vec<serde_json::Value> = serde_json::from_str(json)?;
+
+    Ok(entries.iter().filter_map(|entry| entry["link"].as_str().map(str::to_string)).collect())
+}
+
+/// whether the link points inside the output directory
+fn is_internal(link: &str) -> bool {
+    !link.is_empty() && !link.contains(':') && !link.starts_with("//")
+}
+
+/// resolves `.` and `..` components without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if res.file_name().is_some() => {
+                res.pop();
+            }
+            component => res.push(component),
+        }
+    }
+
+    res
+}
+
+fn link_exists(page: &Path, link: &str, anchors: &mut HashMap<PathBuf, HashSet<String>>) -> Result<bool, Box<dyn std::error::Error>> {
+    let link = link.split('?').next().unwrap_or_default();
+    let (file, anchor) = link.split_once('#').unwrap_or((link, ""));
+
+    let target = if file.is_empty() {
+        page.to_path_buf()
+    }
+    else {
+        normalize(&page.parent().unwrap_or(Path::new("")).join(file))
+    };
+
+    if !target.is_file() {
+        return Ok(false);
+    }
+    if anchor.is_empty() {
+        return Ok(true);
+    }
+
+    if !anchors.contains_key(&target) {
+        let text = fs::read_to_string(&target)?;
+        anchors.insert(target.clone(), attribute_values(&text, "id").into_iter().collect());
+    }
+    let ids = &anchors[&target];
+
+    // `#L2-L4` line ranges of the source pages are highlighted by a script, their first line is the anchor
+    let anchor = match anchor.split_once('-') {
+        Some((first, _)) if anchor.starts_with('L') => first,
+        _ => anchor,
+    };
+
+    Ok(ids.contains(anchor))
+}
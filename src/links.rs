@@ -0,0 +1,497 @@
+use std::{collections::{HashMap, HashSet}, iter, ops::Range};
+
+use pulldown_cmark::{BrokenLink, CowStr, Event, LinkType, Options, Parser, Tag};
+
+use crate::{DocSpan, Function, Implementation, Import, Info, Output, Type, field_anchor, unindent};
+
+/// how many imports may be followed while resolving a single path
+const MAX_IMPORT_DEPTH: usize = 16;
+
+/// the items, submodules and imports of a module used to resolve intra-doc links
+#[derive(Debug, Default)]
+struct ModuleScope {
+    items: Vec<(String, Type)>,
+    /// field names of the structs
+    fields: HashMap<String, Vec<String>>,
+    /// method names of the structs and traits with their ids on the item page
+    methods: HashMap<String, Vec<(String, String)>>,
+    modules: HashMap<String, ModuleScope>,
+    imports: HashMap<String, Vec<String>>,
+}
+
+impl ModuleScope {
+    fn new(items: &[Output], imports: &[Import]) -> Self {
+        let mut scope = ModuleScope::default();
+
+        for item in items.iter() {
+            match &item.information {
+                Info::Module { content, imports, .. } => {
+                    scope.modules.insert(item.name.clone(), ModuleScope::new(content, imports));
+                }
+                Info::Blanc => {}
+                _ => {
+                    if let Some(fields) = item.information.get_fields() {
+                        scope.fields.insert(item.name.clone(), fields.iter().map(|field| field.name.clone()).collect());
+                    }
+                    let mut methods = item.information.get_implementations().unwrap_or_default().into_iter()
+                        .filter(|_| item.r#type == Type::Struct)
+                        .flat_map(|implementation| implementation.functions)
+                        .chain(item.information.get_required_methods().unwrap_or_default())
+                        .chain(item.information.get_provided_methods().unwrap_or_default())
+                        .collect::<Vec<_>>();
+                    // links to a method name point at its first definition on the page
+                    methods.sort_by_key(|method| method.anchor_index);
+                    let methods = methods.iter().map(|method| (method.name.clone(), method.anchor())).collect::<Vec<_>>();
+                    if !methods.is_empty() {
+                        scope.methods.insert(item.name.clone(), methods);
+                    }
+                    scope.items.push((item.name.clone(), item.r#type));
+                }
+            }
+        }
+        for import in imports.iter() {
+            scope.imports.insert(import.alias.clone(), import.path.clone());
+        }
+
+        scope
+    }
+}
+
+/// an item a link points at
+#[derive(Debug, PartialEq)]
+struct Target {
+    /// path of the module containing the item, modules are contained in their parent
+    module: Vec<String>,
+    r#type: Type,
+    name: String,
+    /// section of the item page, e.g. `structfield.x`
+    anchor: Option<String>,
+}
+
+/// how the links to items are written
+#[derive(Debug, Clone)]
+pub(crate) enum LinkStyle {
+    /// relative links to the item pages with the given file extension
+    Pages(&'static str),
+    /// links to the item sections of a single markdown file of the crate with the given name
+    Anchors(String),
+}
+
+/// a link of a doc comment that does not point at any documented item
+#[derive(Debug, PartialEq)]
+pub(crate) struct UnresolvedLink {
+    /// line of the link inside the doc comment, starting from 0
+    pub(crate) line: usize,
+    pub(crate) link: String,
+}
+
+/// resolves the intra-doc links of a crate, e.g. `[Helper]`, ``[`MyStruct::new`]`` or `[HelperDef](trait.HelperDef.html)`
+#[derive(Debug)]
+pub(crate) struct DocLinks {
+    root: ModuleScope,
+    style: LinkStyle,
+}
+
+impl DocLinks {
+    pub(crate) fn new(items: &[Output], imports: &[Import], style: LinkStyle) -> Self {
+        DocLinks { root: ModuleScope::new(items, imports), style }
+    }
+
+    fn scope(&self, module: &[String]) -> Option<&ModuleScope> {
+        module.iter().try_fold(&self.root, |scope, name| scope.modules.get(name))
+    }
+
+    /// finds the item a path written in the module refers to
+    /// paths to fields and methods resolve to their sections of the struct or trait page
+    fn resolve(&self, module: &[String], path: &str) -> Option<Target> {
+        let segments = path.split("::").map(str::trim).collect::<Vec<_>>();
+
+        self.resolve_segments(module.to_vec(), &segments, 0)
+    }
+
+    fn resolve_segments(&self, module: Vec<String>, segments: &[&str], depth: usize) -> Option<Target> {
+        let Some((first, rest)) = segments.split_first() else {
+            let (name, parent) = module.split_last()?;
+            return Some(Target { module: parent.to_vec(), r#type: Type::Module, name: name.clone(), anchor: None });
+        };
+        if depth > MAX_IMPORT_DEPTH {
+            return None;
+        }
+
+        match *first {
+            "crate" => return self.resolve_segments(Vec::new(), rest, depth),
+            "self" => return self.resolve_segments(module, rest, depth),
+            "super" => {
+                let (_, parent) = module.split_last()?;
+                return self.resolve_segments(parent.to_vec(), rest, depth);
+            }
+            _ => {}
+        }
+
+        let scope = self.scope(&module)?;
+
+        if scope.modules.contains_key(*first) {
+            let mut child = module.clone();
+            child.push(first.to_string());
+
+            if let Some(target) = self.resolve_segments(child, rest, depth) {
+                return Some(target);
+            }
+        }
+
+        let item = scope.items.iter().find(|(name, r#type)| {
+            name == first && (rest.is_empty() || matches!(r#type, Type::Struct | Type::Trait))
+        });
+        if let Some((name, r#type)) = item {
+            if rest.len() <= 1 {
+                let is_field = |member: &str| scope.fields.get(name).is_some_and(|fields| fields.iter().any(|field| field == member));
+                let method = |member: &str| scope.methods.get(name).and_then(|methods| methods.iter().find(|(method, _)| method == member));
+                let anchor = match rest.first() {
+                    None => None,
+                    Some(member) if is_field(member) => Some(field_anchor(member)),
+                    Some(member) => match method(member) {
+                        Some((_, anchor)) => Some(anchor.clone()),
+                        // an unknown member is reported instead of linking to the item page
+                        None => return None,
+                    },
+                };
+
+                return Some(Target { module, r#type: *r#type, name: name.clone(), anchor });
+            }
+        }
+
+        // imported paths without `crate::` are tried relative to the module and then to the crate root
+        let import = scope.imports.get(*first)?;
+        let path = import.iter().map(String::as_str).chain(rest.iter().copied()).collect::<Vec<_>>();
+        if path.first() == Some(&"dep") {
+            return None;
+        }
+
+        self.resolve_segments(module, &path, depth + 1)
+            .or_else(|| self.resolve_segments(Vec::new(), &path, depth + 1))
+    }
+
+    /// finds the item of a link target written as a page name, e.g. `trait.HelperDef.html`
+    fn resolve_page(&self, module: &[String], page: &str) -> Option<Target> {
+        let (kind, name) = page.strip_suffix(".html")?.split_once('.')?;
+        let r#type = match kind {
+            "fn" => Type::Function,
+            "struct" => Type::Struct,
+            "trait" => Type::Trait,
+            "global" => Type::Global,
+            "type" => Type::TypeAlias,
+            _ => return None,
+        };
+
+        self.resolve(module, name).filter(|target| target.r#type == r#type && target.name == name)
+    }
+
+    /// link to the target from a page in the `base` module directory
+    fn url(&self, target: &Target, base: &[String]) -> String {
+        let page = self.page_url(target, base);
+
+        match (&target.anchor, &self.style) {
+            (Some(anchor), LinkStyle::Pages(_)) => format!("{}#{}", page, anchor),
+            (Some(anchor), LinkStyle::Anchors(_)) => format!("{}/{}", page, anchor),
+            (None, _) => page,
+        }
+    }
+
+    fn page_url(&self, target: &Target, base: &[String]) -> String {
+        match &self.style {
+            LinkStyle::Pages(extension) => {
+                let common = base.iter().zip(target.module.iter()).take_while(|(a, b)| a == b).count();
+                let up = "../".repeat(base.len() - common);
+                let down = target.module[common..].iter().map(|name| format!("{}/", name)).collect::<String>();
+
+                format!("{}{}{}", up, down, target.r#type.page_file(&target.name, extension))
+            }
+            LinkStyle::Anchors(crate_name) => {
+                let mut path = vec![crate_name.clone()];
+                path.extend(target.module.iter().cloned());
+
+                match target.r#type {
+                    Type::Module => format!("#{}/{}", path.join("/"), target.name),
+                    _ => format!("#{}/{}", path.join("/"), target.r#type.page_file(&target.name, "md").trim_end_matches(".md")),
+                }
+            }
+        }
+    }
+
+    /// resolves a link destination, `None` if it is not meant to be an intra-doc link
+    fn destination(&self, module: &[String], base: &[String], destination: &str) -> Option<Result<String, String>> {
+        let target = if is_page_name(destination) {
+            self.resolve_page(module, destination)
+        }
+        else {
+            let path = item_path(destination)?;
+            self.resolve(module, &path)
+        };
+
+        Some(target.map(|target| self.url(&target, base)).ok_or_else(|| destination.to_string()))
+    }
+
+    /// rewrites the intra-doc links of a doc comment written in the module
+    /// the links are made relative to the page directory of the `base` module
+    pub(crate) fn rewrite(&self, doc: &str, module: &[String], base: &[String]) -> (String, Vec<UnresolvedLink>) {
+        let doc = unindent(doc);
+        let options = Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES | Options::ENABLE_STRIKETHROUGH;
+        let parser = Parser::new_with_broken_link_callback(&doc, options, Some(broken_link));
+
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        let mut unresolved = Vec::new();
+
+        let definitions = parser.reference_definitions().iter()
+            .map(|(_, definition)| (definition.span.clone(), definition.dest.to_string()))
+            .collect::<Vec<_>>();
+        for (span, destination) in definitions {
+            match self.destination(module, base, &destination) {
+                Some(Ok(url)) => {
+                    if let Some(start) = doc[span.clone()].rfind(&destination) {
+                        let start = span.start + start;
+                        edits.push((start..start + destination.len(), url));
+                    }
+                }
+                Some(Err(link)) => unresolved.push((span.start, link)),
+                None => {}
+            }
+        }
+
+        for (event, range) in parser.into_offset_iter() {
+            let Event::Start(Tag::Link { link_type, dest_url, .. }) = event else {
+                continue;
+            };
+            let source = &doc[range.clone()];
+
+            match link_type {
+                LinkType::Inline => {
+                    match self.destination(module, base, &dest_url) {
+                        Some(Ok(url)) => {
+                            if let Some(start) = source.rfind(&*dest_url) {
+                                let start = range.start + start;
+                                edits.push((start..start + dest_url.len(), url));
+                            }
+                        }
+                        Some(Err(link)) => unresolved.push((range.start, link)),
+                        None => {}
+                    }
+                }
+                LinkType::ShortcutUnknown | LinkType::CollapsedUnknown | LinkType::ReferenceUnknown => {
+                    let text_end = match link_type {
+                        LinkType::ReferenceUnknown => source.rfind("][").unwrap_or(source.len() - 1),
+                        _ => source.find(']').unwrap_or(source.len() - 1),
+                    };
+                    let text = &source[1..text_end];
+
+                    match self.destination(module, base, &dest_url) {
+                        Some(Ok(url)) => edits.push((range.clone(), format!("[{}]({})", text, url))),
+                        Some(Err(link)) => unresolved.push((range.start, link)),
+                        None => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+
+        let mut res = String::new();
+        let mut position = 0;
+        for (range, replacement) in edits.into_iter() {
+            if range.start < position {
+                continue;
+            }
+            res.push_str(&doc[position..range.start]);
+            res.push_str(&replacement);
+            position = range.end;
+        }
+        res.push_str(&doc[position..]);
+
+        let mut unresolved = unresolved.into_iter()
+            .map(|(offset, link)| UnresolvedLink { line: doc[..offset].matches('\n').count(), link })
+            .collect::<Vec<_>>();
+        unresolved.sort_by_key(|link| link.line);
+
+        (res, unresolved)
+    }
+
+    /// rewrites the intra-doc links in the docs of the module items and their submodules
+    /// every unresolved link is reported as a warning with the file and the line of the doc comment
+    pub(crate) fn resolve_items(&self, items: &mut [Output], module: &[String]) {
+        for item in items.iter_mut() {
+            // notes of attributes are reported at the item line
+            let item_line = DocSpan::starting_at(&item.span.file, item.span.start_line);
+            item.doc = self.resolve_doc(&item.doc, module, &item.span.doc);
+
+            match &mut item.information {
+                Info::Module { content, .. } => {
+                    let mut child = module.to_vec();
+                    child.push(item.name.clone());
+
+                    self.resolve_items(content, &child);
+                }
+                Info::Function { attributes, .. } => {
+                    self.resolve_deprecation(&mut attributes.deprecated, module, &item_line);
+                }
+                Info::Struct { additional_doc, fields, implementations, deprecated, .. } => {
+                    *additional_doc = self.resolve_doc(additional_doc, module, &item.span.additional_doc);
+                    self.resolve_deprecation(deprecated, module, &item_line);
+                    for field in fields.iter_mut() {
+                        field.doc = self.resolve_doc(&field.doc, module, &field.span.doc);
+                    }
+                    for implementation in implementations.iter_mut() {
+                        self.resolve_functions(&mut implementation.functions, module);
+                    }
+                }
+                Info::Trait { additional_doc, required_methods, provided_methods, implementations, .. } => {
+                    *additional_doc = self.resolve_doc(additional_doc, module, &item.span.additional_doc);
+                    self.resolve_functions(required_methods, module);
+                    self.resolve_functions(provided_methods, module);
+                    for implementation in implementations.iter_mut() {
+                        self.resolve_functions(&mut implementation.functions, module);
+                    }
+                }
+                Info::Global { .. } | Info::TypeAlias { .. } | Info::Blanc => {}
+            }
+        }
+    }
+
+    fn resolve_functions(&self, functions: &mut [Function], module: &[String]) {
+        for function in functions.iter_mut() {
+            function.doc = self.resolve_doc(&function.doc, module, &function.span.doc);
+            self.resolve_deprecation(&mut function.attributes.deprecated, module, &DocSpan::starting_at(&function.span.file, function.span.start_line));
+        }
+    }
+
+    /// rewrites the links in the note of a `#[deprecated]` attribute, unresolved ones are reported at the item line
+    fn resolve_deprecation(&self, deprecated: &mut Option<String>, module: &[String], span: &DocSpan) {
+        if let Some(note) = deprecated {
+            *note = self.resolve_doc(note, module, span);
+        }
+    }
+
+    fn resolve_doc(&self, doc: &str, module: &[String], span: &DocSpan) -> String {
+        if doc.trim().is_empty() {
+            return doc.to_string();
+        }
+
+        let (res, unresolved) = self.rewrite(doc, module, module);
+        for link in unresolved.iter() {
+            eprintln!("warning: {}:{}: unresolved link to `{}`", span.file.display(), span.line(link.line), link.link);
+        }
+
+        res
+    }
+}
+
+/// attaches the impl blocks of the crate to the structs and traits they implement
+/// the types and traits are found the same way as the targets of intra-doc links,
+/// so an impl can be written in any module of the crate
+pub(crate) fn link_implementations(items: &mut [Output], imports: &[Import], impls: Vec<Implementation>) {
+    let mut all = impls.into_iter().map(|implementation| (Vec::new(), implementation)).collect::<Vec<_>>();
+    take_impls(items, &[], &mut all);
+
+    let links = DocLinks::new(items, imports, LinkStyle::Pages("html"));
+
+    for (module, mut implementation) in all.into_iter() {
+        let self_type = links.resolve(&module, &implementation.self_type)
+            .filter(|target| target.r#type == Type::Struct && target.anchor.is_none());
+        let r#trait = implementation.trait_name.as_ref()
+            .and_then(|name| links.resolve(&module, name))
+            .filter(|target| target.r#type == Type::Trait && target.anchor.is_none());
+
+        if let Some(target) = &self_type {
+            implementation.self_path = target.module.iter().cloned().chain(iter::once(target.name.clone())).collect();
+        }
+        if let Some(implementations) = r#trait.and_then(|target| item_implementations(items, &target)) {
+            implementations.push(implementation.clone());
+        }
+        if let Some(implementations) = self_type.and_then(|target| item_implementations(items, &target)) {
+            implementations.push(implementation);
+        }
+    }
+
+    number_methods(items);
+}
+
+/// numbers the methods of every struct that share a name, so that their ids on the struct page are unique
+/// the first inherent definition keeps the `method.<name>` id, the others are `method.<name>-<n>`
+fn number_methods(items: &mut [Output]) {
+    for item in items.iter_mut() {
+        match &mut item.information {
+            Info::Module { content, .. } => number_methods(content),
+            Info::Struct { implementations, .. } => {
+                let mut inherent = HashSet::new();
+                let mut counts: HashMap<String, usize> = HashMap::new();
+
+                // the struct page lists the inherent implementations before the trait implementations
+                let (inherent_impls, trait_impls): (Vec<_>, Vec<_>) = implementations.iter_mut().partition(|implementation| !implementation.is_trait_impl());
+                for implementation in inherent_impls.into_iter().chain(trait_impls) {
+                    let is_trait_impl = implementation.is_trait_impl();
+
+                    for function in implementation.functions.iter_mut() {
+                        function.anchor_index = if !is_trait_impl && inherent.insert(function.name.clone()) {
+                            0
+                        }
+                        else {
+                            let count = counts.entry(function.name.clone()).or_default();
+                            *count += 1;
+                            *count
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// removes the impl blocks from the modules together with the path of the module they are written in
+fn take_impls(items: &mut [Output], module: &[String], res: &mut Vec<(Vec<String>, Implementation)>) {
+    for item in items.iter_mut() {
+        if let Info::Module { content, impls, .. } = &mut item.information {
+            let mut child = module.to_vec();
+            child.push(item.name.clone());
+
+            res.extend(std::mem::take(impls).into_iter().map(|implementation| (child.clone(), implementation)));
+            take_impls(content, &child, res);
+        }
+    }
+}
+
+fn item_implementations<'a>(items: &'a mut [Output], target: &Target) -> Option<&'a mut Vec<Implementation>> {
+    let items = target.module.iter().try_fold(items, |items, name| {
+        match items.iter_mut().find(|item| matches!(item.r#type, Type::Module | Type::Contract) && &item.name == name).map(|item| &mut item.information) {
+            Some(Info::Module { content, .. }) => Some(content.as_mut_slice()),
+            _ => None,
+        }
+    })?;
+
+    match items.iter_mut().find(|item| item.r#type == target.r#type && item.name == target.name).map(|item| &mut item.information) {
+        Some(Info::Struct { implementations, .. } | Info::Trait { implementations, .. }) => Some(implementations),
+        _ => None,
+    }
+}
+
+/// keeps the links without a reference definition, e.g. `[Helper]`, with the reference as their destination
+fn broken_link(link: BrokenLink<'_>) -> Option<(CowStr<'_>, CowStr<'_>)> {
+    Some((link.reference, CowStr::from("")))
+}
+
+/// whether the destination is written as the name of an item page, e.g. `struct.Helper.html`
+fn is_page_name(destination: &str) -> bool {
+    ["fn.", "struct.", "trait.", "global.", "type."].iter().any(|prefix| destination.starts_with(prefix)) &&
+        destination.ends_with(".html") &&
+        !destination.contains('/')
+}
+
+/// the item path of a link written as ``[`Helper`]`` or `[Helper::new()]`, `None` if the text is not a path
+fn item_path(text: &str) -> Option<String> {
+    let path = text.trim().trim_matches('`').trim_end_matches("()");
+    let is_path = path.split("::").all(|segment| {
+        segment.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') &&
+            segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    });
+
+    is_path.then(|| path.to_string())
+}
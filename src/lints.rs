@@ -0,0 +1,315 @@
+use std::{collections::HashMap, fmt, fs, path::{Path, PathBuf}};
+
+use noirc_frontend::token::{DocComments, Keyword, SpannedToken, Token};
+use serde_json::json;
+
+use crate::{DocSpan, Function, Info, ItemVisibility, Level, Output, Type, code_blocks, lex, line};
+
+/// a check of the doc comments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum Lint {
+    /// public items without docs
+    MissingDocs,
+    /// doc comments containing only whitespace
+    EmptyDocs,
+    /// `//!` comments that document neither a file nor an item
+    MisplacedInnerDocs,
+    /// `/** */` block comments mixed with `///` or `//!` line comments
+    MixedDocComments,
+    /// code blocks without a closing fence
+    UnbalancedCodeFences,
+}
+
+impl Lint {
+    pub(crate) const ALL: [Lint; 5] = [Lint::MissingDocs, Lint::EmptyDocs, Lint::MisplacedInnerDocs, Lint::MixedDocComments, Lint::UnbalancedCodeFences];
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Lint::MissingDocs => "missing_docs",
+            Lint::EmptyDocs => "empty_docs",
+            Lint::MisplacedInnerDocs => "misplaced_inner_docs",
+            Lint::MixedDocComments => "mixed_doc_comments",
+            Lint::UnbalancedCodeFences => "unbalanced_code_fences",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Lint::MissingDocs => "Public items should be documented.",
+            Lint::EmptyDocs => "Doc comments should not contain only whitespace.",
+            Lint::MisplacedInnerDocs => "`//!` comments should be written at the beginning of a file or a module, or right before an item.",
+            Lint::MixedDocComments => "A doc comment should not mix `/** */` block comments with `///` or `//!` line comments.",
+            Lint::UnbalancedCodeFences => "Code blocks of doc comments should be closed.",
+        }
+    }
+}
+
+/// how the diagnostics of the lints are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LintFormat {
+    /// a line per diagnostic
+    Text,
+    /// a SARIF log for code scanning tools
+    Sarif,
+}
+
+/// levels of the lints, every lint warns by default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: HashMap<Lint, Level>,
+}
+
+impl Default for LintLevels {
+    fn default() -> Self {
+        LintLevels { levels: Lint::ALL.iter().map(|lint| (*lint, Level::Warn)).collect() }
+    }
+}
+
+impl LintLevels {
+    pub(crate) fn set(&mut self, lints: &[Lint], level: Level) {
+        for lint in lints.iter() {
+            self.levels.insert(*lint, level);
+        }
+    }
+
+    pub(crate) fn get(&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+}
+
+/// a problem found by a lint
+#[derive(Debug, PartialEq)]
+pub(crate) struct Diagnostic {
+    pub(crate) lint: Lint,
+    pub(crate) level: Level,
+    pub(crate) file: PathBuf,
+    pub(crate) line: usize,
+    pub(crate) message: String,
+}
+
+impl Diagnostic {
+    fn severity(&self) -> &'static str {
+        match self.level {
+            Level::Deny => "error",
+            _ => "warning",
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}:{}: {}", self.severity(), self.lint.name(), self.file.display(), self.line, self.message)
+    }
+}
+
+/// collects the diagnostics of a crate
+/// the items are checked together with the tokens of every file they are written in
+struct Linter<'a> {
+    levels: &'a LintLevels,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, lint: Lint, file: &Path, line: usize, message: String) {
+        let level = self.levels.get(lint);
+        if level != Level::Allow {
+            self.diagnostics.push(Diagnostic { lint, level, file: file.to_path_buf(), line, message });
+        }
+    }
+
+    /// `public` tells whether the items are reachable from the crate root through `pub` items
+    fn items(&mut self, items: &[Output], public: bool) {
+        for item in items.iter() {
+            self.code_fences(&item.doc, &item.span.doc);
+            if let Some(additional_doc) = item.information.get_additional_doc() {
+                self.code_fences(&additional_doc, &item.span.additional_doc);
+            }
+
+            let kind = match item.r#type {
+                Type::Function => "function",
+                Type::Module => "module",
+                Type::Contract => "contract",
+                Type::Struct => "struct",
+                Type::Trait => "trait",
+                Type::Global => "global",
+                Type::TypeAlias => "type alias",
+                Type::OuterComment => continue,
+            };
+            let public = public && item.visibility == ItemVisibility::Public;
+            if public && !is_documented(item) {
+                self.report(Lint::MissingDocs, &item.span.file, item.span.start_line, format!("public {} `{}` is not documented", kind, item.name));
+            }
+
+            match &item.information {
+                Info::Module { content, .. } => self.items(content, public),
+                Info::Struct { fields, implementations, .. } => {
+                    for field in fields.iter().filter(|field| public && field.visibility == ItemVisibility::Public && field.doc.trim().is_empty()) {
+                        self.report(Lint::MissingDocs, &field.span.file, field.span.start_line, format!("public field `{}::{}` is not documented", item.name, field.name));
+                    }
+                    // methods of trait implementations are documented by the trait
+                    for implementation in implementations.iter().filter(|implementation| implementation.trait_name.is_none()) {
+                        self.methods(&item.name, &implementation.functions, public);
+                    }
+                }
+                Info::Trait { required_methods, provided_methods, .. } => {
+                    self.methods(&item.name, required_methods, public);
+                    self.methods(&item.name, provided_methods, public);
+                }
+                Info::Function { .. } | Info::Global { .. } | Info::TypeAlias { .. } | Info::Blanc => {}
+            }
+        }
+    }
+
+    fn methods(&mut self, parent: &str, methods: &[Function], public: bool) {
+        for method in methods.iter() {
+            self.code_fences(&method.doc, &method.span.doc);
+
+            if public && method.visibility == ItemVisibility::Public && method.doc.trim().is_empty() {
+                self.report(Lint::MissingDocs, &method.span.file, method.span.start_line, format!("public method `{}::{}` is not documented", parent, method.name));
+            }
+        }
+    }
+
+    fn code_fences(&mut self, doc: &str, span: &DocSpan) {
+        for block in code_blocks(doc).iter().filter(|block| !block.closed) {
+            self.report(Lint::UnbalancedCodeFences, &span.file, span.line(block.fence_line), "code block is not closed".to_string());
+        }
+    }
+
+    /// checks the runs of doc comments written one after another
+    fn tokens(&mut self, file: &Path, text: &str, tokens: &[SpannedToken]) {
+        let mut i = 0;
+
+        while i < tokens.len() {
+            if !matches!(tokens[i].token(), Token::DocComment(_)) {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while i < tokens.len() && matches!(tokens[i].token(), Token::DocComment(_) | Token::LineComment(..) | Token::BlockComment(..)) {
+                i += 1;
+            }
+            let comments = tokens[start..i].iter().filter_map(|token| match token.token() {
+                Token::DocComment(doc) => Some(doc),
+                _ => None,
+            }).collect::<Vec<_>>();
+            let line = line(text, tokens[start].to_span().start());
+
+            let content = |doc: &DocComments| match doc {
+                DocComments::Single(doc) | DocComments::Block(doc) | DocComments::Outer(doc) => doc.clone(),
+            };
+            if comments.iter().all(|doc| content(doc).trim().is_empty()) {
+                self.report(Lint::EmptyDocs, file, line, "doc comment contains only whitespace".to_string());
+            }
+
+            let is_block = |doc: &&DocComments| matches!(doc, DocComments::Block(_));
+            if comments.iter().any(is_block) && !comments.iter().all(is_block) {
+                self.report(Lint::MixedDocComments, file, line, "doc comment mixes block and line comments".to_string());
+            }
+
+            let has_inner_docs = comments.iter().any(|doc| matches!(doc, DocComments::Outer(_)));
+            if has_inner_docs && !is_module_start(tokens, start) && !is_item_header(tokens.get(i)) {
+                self.report(Lint::MisplacedInnerDocs, file, line, "`//!` comment documents neither a module nor an item".to_string());
+            }
+        }
+    }
+}
+
+/// the item has a doc comment, structs and traits can also be documented by their `//!` comments
+/// and modules by the `//!` comments of their beginning
+pub(crate) fn is_documented(item: &Output) -> bool {
+    if !item.doc.trim().is_empty() || item.information.get_additional_doc().is_some_and(|doc| !doc.trim().is_empty()) {
+        return true;
+    }
+
+    match &item.information {
+        Info::Module { content, .. } => content.first().is_some_and(|first| first.r#type == Type::OuterComment && !first.doc.trim().is_empty()),
+        _ => false,
+    }
+}
+
+/// the token is at the beginning of a file or of an inline `mod name { ... }`
+fn is_module_start(tokens: &[SpannedToken], i: usize) -> bool {
+    let previous = tokens[..i].iter().rev()
+        .filter(|token| !matches!(token.token(), Token::LineComment(..) | Token::BlockComment(..)))
+        .take(3)
+        .map(|token| token.token())
+        .collect::<Vec<_>>();
+
+    matches!(previous.as_slice(), [] | [Token::LeftBrace, Token::Ident(_), Token::Keyword(Keyword::Mod | Keyword::Contract), ..])
+}
+
+/// `//!` comments right before an item header are its additional doc
+fn is_item_header(token: Option<&SpannedToken>) -> bool {
+    matches!(token.map(|token| token.token()), Some(Token::Keyword(_) | Token::Attribute(_)))
+}
+
+/// the files of the modules of the crate
+pub(crate) fn module_files(files: &mut Vec<PathBuf>, items: &[Output]) {
+    for item in items.iter() {
+        if let Info::Module { content, .. } = &item.information {
+            if !files.contains(&item.span.file) {
+                files.push(item.span.file.clone());
+            }
+            module_files(files, content);
+        }
+    }
+}
+
+/// runs the lints over the items and the files of a crate
+pub(crate) fn lint_crate(entry: &Path, items: &[Output], levels: &LintLevels) -> Result<Vec<Diagnostic>, Box<dyn std::error::Error>> {
+    let mut linter = Linter { levels, diagnostics: Vec::new() };
+    linter.items(items, true);
+    let mut diagnostics = linter.diagnostics;
+
+    let mut files = vec![entry.to_path_buf()];
+    module_files(&mut files, items);
+
+    for file in files.iter() {
+        let text = fs::read_to_string(file).map_err(|err| format!("failed to open {}: {}", file.display(), err))?;
+        diagnostics.extend(lint_tokens(file, &text, &lex(file, &text)?, levels));
+    }
+
+    diagnostics.sort_by(|a, b| (&a.file, a.line, a.lint).cmp(&(&b.file, b.line, b.lint)));
+
+    Ok(diagnostics)
+}
+
+/// runs the lints checking the comments of a file
+pub(crate) fn lint_tokens(file: &Path, text: &str, tokens: &[SpannedToken], levels: &LintLevels) -> Vec<Diagnostic> {
+    let mut linter = Linter { levels, diagnostics: Vec::new() };
+    linter.tokens(file, text, tokens);
+
+    linter.diagnostics
+}
+
+/// the diagnostics as a SARIF 2.1.0 log
+pub(crate) fn sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let rules = Lint::ALL.iter().map(|lint| json!({
+        "id": lint.name(),
+        "shortDescription": { "text": lint.description() },
+    })).collect::<Vec<_>>();
+
+    let results = diagnostics.iter().map(|diagnostic| json!({
+        "ruleId": diagnostic.lint.name(),
+        "level": diagnostic.severity(),
+        "message": { "text": diagnostic.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": diagnostic.file.to_string_lossy().replace('\\', "/") },
+                "region": { "startLine": diagnostic.line },
+            },
+        }],
+    })).collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION"), "rules": rules } },
+            "results": results,
+        }],
+    })
+}
@@ -1,26 +1,180 @@
+mod cli;
+mod contracts;
+mod coverage;
+mod doc_tests;
 mod getters;
+mod json;
+mod link_check;
+mod links;
+mod lints;
+mod markdown;
+mod nargo;
 mod output;
 mod pages_generation;
+mod search;
 mod tests;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, path::{Path, PathBuf}, process::ExitCode};
+use clap::Parser;
+use cli::*;
+use contracts::*;
+use coverage::*;
+use doc_tests::*;
 use getters::*;
+use json::*;
+use link_check::*;
+use links::*;
+use lints::*;
+use markdown::*;
+use nargo::*;
 use output::*;
 use pages_generation::*;
+use search::*;
+
+/// format of the generated documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// html pages
+    Html,
+    /// a single json file per crate
+    Json,
+    /// markdown files
+    Markdown,
+}
+
+/// how a problem found in the documentation is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Level {
+    /// not reported
+    Allow,
+    /// printed as a warning
+    Warn,
+    /// printed as an error, the generation fails
+    Deny,
+}
+
+/// settings of the documentation generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub output_dir: PathBuf,
+    pub format: OutputFormat,
+    /// writes the markdown documentation of a crate to a single file
+    pub single_file: bool,
+    /// how links of the html pages pointing at missing pages or anchors are reported
+    pub broken_links: Level,
+    /// documents all items instead of the items reachable from the crate root through `pub` items
+    pub document_private_items: bool,
+    /// lists the `#[test]` functions in a separate section of the module pages instead of leaving them out
+    pub document_tests: bool,
+    /// directory the source pages and the json spans are relative to, the directory of the entry file if not set
+    pub source_root: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { output_dir: PathBuf::from("generated_doc"), format: OutputFormat::Html, single_file: false, broken_links: Level::Warn, document_private_items: false, document_tests: false, source_root: None }
+    }
+}
 
 /// the main function of the program
 /// generates all documentation files
 /// the input file is a file with a Noir code
-pub fn generate_doc(input_file: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let doc = get_doc(input_file).unwrap();
+/// the pages are written to the `<output dir>/<crate name>` directory, every module
+/// gets its own directory with an `index.html` page and `fn.*`, `struct.*` and `trait.*` item pages
+/// with the json format the whole crate is written to `<output dir>/<crate name>.json` instead,
+/// the markdown format uses the same layout as the html pages with `.md` files
+pub fn generate_doc(input_file: &Path, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = extract_filename(input_file).ok_or("invalid input file name")?.to_string();
+
+    generate_crate_doc(input_file, filename, config)?;
+
+    report_broken_links(config)
+}
+
+/// generates documentation for a Nargo package or every member of a Nargo workspace
+/// each package is documented in its own directory inside the output directory
+pub fn generate_project_doc(program_dir: &Path, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = resolve_workspace(program_dir)?;
+
+    for package in workspace.members.iter() {
+        generate_crate_doc(&package.entry_path, package.name.clone(), config)?;
+    }
+
+    if workspace.is_workspace && config.format == OutputFormat::Html {
+        let name = workspace.root_dir.file_name().and_then(|name| name.to_str()).unwrap_or("workspace").to_string();
+
+        generate_workspace_page(WorkspaceIndex { name, packages: workspace.members, assets: ASSETS_DIR.to_string() }, &config.output_dir)?;
+    }
+
+    report_broken_links(config)
+}
+
+/// checks the links of the generated html pages and reports the broken ones
+fn report_broken_links(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    if config.format != OutputFormat::Html || config.broken_links == Level::Allow {
+        return Ok(());
+    }
+
+    let broken_links = check_links(&config.output_dir)?;
+
+    for link in broken_links.iter() {
+        match config.broken_links {
+            Level::Deny => eprintln!("error: {}", link),
+            _ => eprintln!("warning: {}", link),
+        }
+    }
+
+    if config.broken_links == Level::Deny && !broken_links.is_empty() {
+        return Err(format!("{} broken links in the generated documentation", broken_links.len()).into());
+    }
+
+    Ok(())
+}
+
+fn generate_crate_doc(entry: &Path, name: String, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = get_doc(entry)?;
+
+    let location = ModuleLocation::crate_root(entry);
+
+    let mut tokens = Output::to_output(&doc, &location)?;
+    if !config.document_tests {
+        tokens = without_tests(tokens);
+    }
+    if !config.document_private_items {
+        tokens = public_api(tokens);
+    }
+    let imports = imports(&doc.module.items);
+    let src_root = config.source_root.clone().unwrap_or_else(|| location.child_dir.clone());
+
+    fs::create_dir_all(&config.output_dir)?;
+    fs::write(config.output_dir.join(OUTPUT_MARKER), "")?;
+
+    match config.format {
+        OutputFormat::Html => {
+            let page = ModulePage::crate_root(config.output_dir.join(&name), src_root);
 
-    let tokens = Output::to_output(doc);
+            let links = DocLinks::new(&tokens, &imports, LinkStyle::Pages("html"));
+            let index = search_index(&name, &tokens, &links);
+            links.resolve_items(&mut tokens, &[]);
 
-    let filename = extract_filename(input_file).unwrap().to_string();
+            let out = AllOutput{ all_output: tokens, filename: name, span: doc.file_span(), source_link: String::new(), assets: String::new(), search_page: String::new() };
 
-    let out = AllOutput{ all_output: tokens.clone(), filename };
+            generate_module_page(out, &page)?;
+            generate_search_page(&index, &page.crate_dir)?;
+            generate_assets(&config.output_dir)?;
+        }
+        OutputFormat::Json => {
+            let json = JsonCrate::new(name, &tokens, &doc.file_span(), &src_root);
 
-    generate_module_page(out)?;
+            generate_json(&json, &config.output_dir)?;
+        }
+        OutputFormat::Markdown => {
+            let style = if config.single_file { LinkStyle::Anchors(name.clone()) } else { LinkStyle::Pages("md") };
+            DocLinks::new(&tokens, &imports, style).resolve_items(&mut tokens, &[]);
+
+            generate_markdown(&name, &tokens, &doc.file_span(), &src_root, &config.output_dir, config.single_file)?;
+        }
+    }
 
     Ok(())
 }
@@ -32,22 +186,278 @@ pub struct Map {
 
 /// returns all necessary information for generating documentation
 /// the input file is a file with a Noir code
-pub fn get_map(input_file: &str) -> Map {
+pub fn get_map(input_file: &Path) -> Result<Map, Box<dyn std::error::Error>> {
     let mut map = HashMap::new();
 
-    let doc = get_doc(input_file).unwrap();
+    let doc = get_doc(input_file)?;
 
-    let tokens = Output::to_output(doc);
+    let tokens = Output::to_output(&doc, &ModuleLocation::crate_root(input_file))?;
 
     for token in tokens.iter() {
         map.insert(token.information.clone(), token.doc.clone());
     }
 
-    Map { map }
+    Ok(Map { map })
+}
+
+/// returns the documentation information of every package in a Nargo package or workspace
+pub fn get_project_map(program_dir: &Path) -> Result<Vec<(String, Map)>, Box<dyn std::error::Error>> {
+    let workspace = resolve_workspace(program_dir)?;
+    let mut maps = Vec::new();
+
+    for package in workspace.members.iter() {
+        maps.push((package.name.clone(), get_map(&package.entry_path)?));
+    }
+
+    Ok(maps)
+}
+
+/// checks the doc comments of a crate and prints the diagnostics
+/// fails when a lint with the deny level finds a problem
+pub fn lint(input_file: &Path, levels: &LintLevels, format: LintFormat) -> Result<(), Box<dyn std::error::Error>> {
+    report_diagnostics(&crate_diagnostics(input_file, levels)?, format)
+}
+
+/// checks the doc comments of a Nargo package or every member of a Nargo workspace
+pub fn lint_project(program_dir: &Path, levels: &LintLevels, format: LintFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = resolve_workspace(program_dir)?;
+    let mut diagnostics = Vec::new();
+
+    for package in workspace.members.iter() {
+        diagnostics.extend(crate_diagnostics(&package.entry_path, levels)?);
+    }
+
+    report_diagnostics(&diagnostics, format)
+}
+
+fn crate_diagnostics(entry: &Path, levels: &LintLevels) -> Result<Vec<Diagnostic>, Box<dyn std::error::Error>> {
+    let doc = get_doc(entry)?;
+    let tokens = Output::to_output(&doc, &ModuleLocation::crate_root(entry))?;
+
+    lint_crate(entry, &tokens, levels)
+}
+
+fn report_diagnostics(diagnostics: &[Diagnostic], format: LintFormat) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        LintFormat::Text => {
+            for diagnostic in diagnostics.iter() {
+                eprintln!("{}", diagnostic);
+            }
+        }
+        LintFormat::Sarif => {
+            println!("{}", serde_json::to_string_pretty(&sarif(diagnostics))?);
+        }
+    }
+
+    let errors = diagnostics.iter().filter(|diagnostic| diagnostic.level == Level::Deny).count();
+    if errors > 0 {
+        return Err(format!("{} problems found by denied lints", errors).into());
+    }
+
+    Ok(())
+}
+
+/// prints the documentation coverage of a crate
+/// fails when the coverage is below `min_coverage` percent
+pub fn coverage(input_file: &Path, format: CoverageFormat, min_coverage: Option<f64>) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = extract_filename(input_file).ok_or("invalid input file name")?.to_string();
+
+    report_coverage(&[crate_coverage(input_file, &filename)?], format, min_coverage)
+}
+
+/// prints the documentation coverage of a Nargo package or every member of a Nargo workspace
+pub fn project_coverage(program_dir: &Path, format: CoverageFormat, min_coverage: Option<f64>) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = resolve_workspace(program_dir)?;
+    let mut packages = Vec::new();
+
+    for package in workspace.members.iter() {
+        packages.push(crate_coverage(&package.entry_path, &package.name)?);
+    }
+
+    report_coverage(&packages, format, min_coverage)
+}
+
+fn crate_coverage(entry: &Path, name: &str) -> Result<PackageCoverage, Box<dyn std::error::Error>> {
+    let doc = get_doc(entry)?;
+    let tokens = Output::to_output(&doc, &ModuleLocation::crate_root(entry))?;
+
+    Ok(PackageCoverage::new(name, &public_api(tokens)))
+}
+
+fn report_coverage(packages: &[PackageCoverage], format: CoverageFormat, min_coverage: Option<f64>) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        CoverageFormat::Table => {
+            for package in packages.iter() {
+                println!("{}\n{}", package.name, package.table());
+            }
+        }
+        CoverageFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(packages)?);
+        }
+    }
+
+    let Some(min_coverage) = min_coverage else {
+        return Ok(());
+    };
+    let below = packages.iter().filter(|package| package.coverage < min_coverage)
+        .map(|package| format!("{} ({:.1}%)", package.name, package.coverage))
+        .collect::<Vec<_>>();
+
+    if !below.is_empty() {
+        return Err(format!("documentation coverage is below {}%: {}", min_coverage, below.join(", ")).into());
+    }
+
+    Ok(())
+}
+
+/// runs the doc tests of a crate and prints their results
+/// the input file is a file with a Noir code
+pub fn test_doc(input_file: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = extract_filename(input_file).ok_or("invalid input file name")?.to_string();
+
+    report_doc_tests(&run_doc_tests(input_file, &filename)?)
+}
+
+/// runs the doc tests of a Nargo package or every member of a Nargo workspace
+pub fn test_project_doc(program_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = resolve_workspace(program_dir)?;
+    let mut failed = Vec::new();
+
+    for package in workspace.members.iter() {
+        failed.extend(run_doc_tests(&package.entry_path, &package.name)?);
+    }
+
+    report_doc_tests(&failed)
 }
 
-fn main() {
-    generate_doc("input_files/prog.nr").unwrap();
+/// runs the doc tests of the crate, prints the result of every test and returns the failed ones
+fn run_doc_tests(entry: &Path, name: &str) -> Result<Vec<(DocTest, String)>, Box<dyn std::error::Error>> {
+    let doc = get_doc(entry)?;
+    let tokens = Output::to_output(&doc, &ModuleLocation::crate_root(entry))?;
+
+    let krate = TestCrate::new(name, entry, &tokens)?;
+
+    let tests = doc_tests(name, &tokens);
+    let (mut passed, mut ignored) = (0, 0);
+    let mut failed = Vec::new();
+
+    println!("running {} doc tests of {}", tests.len(), name);
+    for test in tests.into_iter() {
+        match test.run(&krate) {
+            DocTestResult::Passed => {
+                println!("test {} ... ok", test);
+                passed += 1;
+            }
+            DocTestResult::Ignored => {
+                println!("test {} ... ignored", test);
+                ignored += 1;
+            }
+            DocTestResult::Failed(err) => {
+                println!("test {} ... FAILED", test);
+                failed.push((test, err));
+            }
+        }
+    }
+
+    let status = if failed.is_empty() { "ok" } else { "FAILED" };
+    println!("\ntest result: {}. {} passed; {} failed; {} ignored\n", status, passed, failed.len(), ignored);
+
+    Ok(failed)
+}
+
+fn report_doc_tests(failed: &[(DocTest, String)]) -> Result<(), Box<dyn std::error::Error>> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+
+    println!("failures:");
+    for (test, err) in failed.iter() {
+        println!("    {}: {}", test, err);
+    }
+
+    Err(format!("{} doc tests failed", failed.len()).into())
+}
 
-    dbg!(get_map("input_files/struct_example.nr"));
+/// file `build` writes into the output directory, `clean` only removes directories that have it
+pub(crate) const OUTPUT_MARKER: &str = ".doc-generator";
+
+/// removes the directory with the generated documentation
+/// a directory without the marker file of `build` is left untouched
+pub fn clean(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if !output_dir.exists() {
+        return Ok(());
+    }
+    if !output_dir.join(OUTPUT_MARKER).is_file() {
+        return Err(format!("{} was not generated by `build`, it has no {} file", output_dir.display(), OUTPUT_MARKER).into());
+    }
+
+    fs::remove_dir_all(output_dir)?;
+
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match cli.command {
+        Command::Build(args) => {
+            let config = Config { output_dir: args.output_dir, format: args.output_format, single_file: args.single_file, broken_links: args.broken_links, document_private_items: args.document_private_items, document_tests: args.document_tests, source_root: args.source_root };
+
+            match args.program_dir {
+                Some(program_dir) => generate_project_doc(&program_dir, &config),
+                None => generate_doc(&args.entry, &config),
+            }
+        }
+        Command::Map(args) => {
+            if args.source_root.is_some() {
+                eprintln!("warning: `--source-root` is deprecated and ignored, modules are looked up next to the file declaring them");
+            }
+            match args.program_dir {
+                Some(program_dir) => {
+                    for (name, map) in get_project_map(&program_dir)? {
+                        println!("{}: {:#?}", name, map);
+                    }
+                }
+                None => {
+                    let map = get_map(&args.entry)?;
+                    println!("{:#?}", map);
+                }
+            }
+            Ok(())
+        }
+        Command::Lint(args) => {
+            let mut levels = LintLevels::default();
+            levels.set(&args.allow, Level::Allow);
+            levels.set(&args.warn, Level::Warn);
+            levels.set(&args.deny, Level::Deny);
+
+            match args.program_dir {
+                Some(program_dir) => lint_project(&program_dir, &levels, args.format),
+                None => lint(&args.entry, &levels, args.format),
+            }
+        }
+        Command::Coverage(args) => {
+            match args.program_dir {
+                Some(program_dir) => project_coverage(&program_dir, args.format, args.min_coverage),
+                None => coverage(&args.entry, args.format, args.min_coverage),
+            }
+        }
+        Command::Test(args) => {
+            match args.program_dir {
+                Some(program_dir) => test_project_doc(&program_dir),
+                None => test_doc(&args.entry),
+            }
+        }
+        Command::Clean(args) => {
+            clean(&args.output_dir)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,316 @@
+use std::{fs::{self, File}, io::Write, iter, path::Path};
+
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::{contract_sections, hide_doc_test_lines, Function, Implementation, ItemSection, Output, SourceSpan, Type};
+
+/// sections of the module tables in the order they are written
+const SECTIONS: [(Type, &str); 7] = [
+    (Type::Function, "Functions"),
+    (Type::Module, "Modules"),
+    (Type::Contract, "Contracts"),
+    (Type::Struct, "Structs"),
+    (Type::Trait, "Traits"),
+    (Type::Global, "Globals"),
+    (Type::TypeAlias, "Type aliases"),
+];
+
+/// writes the crate documentation as markdown
+/// either one `.md` file per module and item in the same layout as the html pages,
+/// or a single `<output dir>/<crate name>.md` file
+pub(crate) fn generate_markdown(name: &str, items: &[Output], span: &SourceSpan, src_root: &Path, output_dir: &Path, single_file: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let writer = MarkdownWriter { src_root, single_file };
+    let path = vec![name.to_string()];
+
+    if single_file {
+        let mut out = String::new();
+        writer.module(&mut out, &path, Type::Module, items, span);
+
+        fs::create_dir_all(output_dir)?;
+        write_file(&output_dir.join(format!("{}.md", name)), &out)
+    }
+    else {
+        writer.module_pages(&output_dir.join(name), &path, Type::Module, items, span)
+    }
+}
+
+fn write_file(file_name: &Path, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = File::create(file_name)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(())
+}
+
+/// removes the indentation shared by all non-empty lines of a doc comment
+/// `/// text` comments keep the space after the slashes, which would otherwise shift indented code blocks
+pub(crate) fn unindent(doc: &str) -> String {
+    let indent = doc.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    doc.lines().map(|line| line.get(indent..).unwrap_or_default()).collect::<Vec<_>>().join("\n")
+}
+
+/// the first paragraph of a doc comment on a single line
+fn first_paragraph(doc: &str) -> String {
+    let doc = unindent(doc);
+    let paragraph = doc.trim().split("\n\n").next().unwrap_or_default();
+
+    paragraph.lines().map(str::trim).collect::<Vec<_>>().join(" ")
+}
+
+/// the first paragraph of a doc comment, usable inside a markdown table cell
+pub(crate) fn summary(doc: &str) -> String {
+    first_paragraph(doc).replace('|', "\\|")
+}
+
+/// the doc comment as it is shown in the documentation
+fn shown_doc(doc: &str) -> String {
+    hide_doc_test_lines(&unindent(doc)).trim().to_string()
+}
+
+/// renders a doc comment as commonmark with tables, footnotes and strikethrough
+pub(crate) fn render_html(doc: &str) -> String {
+    let doc = hide_doc_test_lines(&unindent(doc));
+    let options = Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES | Options::ENABLE_STRIKETHROUGH;
+
+    let mut res = String::new();
+    html::push_html(&mut res, Parser::new_ext(&doc, options));
+
+    res
+}
+
+/// renders the first paragraph of a doc comment without the enclosing `<p>` tag
+pub(crate) fn summary_html(doc: &str) -> String {
+    let res = render_html(&first_paragraph(doc));
+
+    res.trim_end().trim_start_matches("<p>").trim_end_matches("</p>").to_string()
+}
+
+struct MarkdownWriter<'a> {
+    src_root: &'a Path,
+    single_file: bool,
+}
+
+impl MarkdownWriter<'_> {
+    fn module_pages(&self, dir: &Path, path: &[String], kind: Type, items: &[Output], span: &SourceSpan) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(dir)?;
+
+        let mut page = String::new();
+        self.module(&mut page, path, kind, items, span);
+        write_file(&dir.join("index.md"), &page)?;
+
+        for item in items.iter() {
+            match item.r#type {
+                Type::Module | Type::Contract => {
+                    let mut child = path.to_vec();
+                    child.push(item.name.clone());
+
+                    self.module_pages(&dir.join(&item.name), &child, item.r#type, &item.information.get_content().unwrap(), &item.span)?;
+                }
+                Type::OuterComment => {}
+                _ => {
+                    let mut page = String::new();
+                    self.item(&mut page, path, item);
+
+                    write_file(&dir.join(item.r#type.page_file(&item.name, "md")), &page)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// writes the module or contract heading, docs and item tables
+    /// in a single file the items and the submodules follow the tables
+    fn module(&self, out: &mut String, path: &[String], kind: Type, items: &[Output], span: &SourceSpan) {
+        let level = if self.single_file && path.len() > 1 { 2 } else { 1 };
+
+        if self.single_file {
+            out.push_str(&format!("<a id=\"{}\"></a>\n\n", path.join("/")));
+        }
+        out.push_str(&format!("{} {} `{}`\n\n", "#".repeat(level), kind, path.join("::")));
+
+        for item in items.iter().filter(|item| item.r#type == Type::OuterComment) {
+            out.push_str(&format!("{}\n\n", shown_doc(&item.doc)));
+        }
+        out.push_str(&self.defined_in(span));
+
+        let sections = match kind {
+            Type::Contract => contract_sections(items),
+            _ => module_sections(items),
+        };
+
+        for section in sections {
+            out.push_str(&format!("{} {}\n\n| Name | Description |\n| --- | --- |\n", "#".repeat(level + 1), section.title));
+            for item in section.items.iter() {
+                let deprecated = if item.is_deprecated() { " *(deprecated)*" } else { "" };
+                out.push_str(&format!("| [{}]({}){} | {} |\n", item.name, self.link(path, item), deprecated, summary(&item.doc)));
+            }
+            out.push('\n');
+        }
+
+        if self.single_file {
+            for item in items.iter() {
+                match item.r#type {
+                    Type::Module | Type::Contract => {
+                        let mut child = path.to_vec();
+                        child.push(item.name.clone());
+
+                        self.module(out, &child, item.r#type, &item.information.get_content().unwrap(), &item.span);
+                    }
+                    Type::OuterComment => {}
+                    _ => {
+                        self.item(out, path, item);
+                    }
+                }
+            }
+        }
+    }
+
+    /// writes the page or the section of a function, struct or trait
+    fn item(&self, out: &mut String, path: &[String], item: &Output) {
+        let level = if self.single_file { 3 } else { 1 };
+
+        if self.single_file {
+            out.push_str(&format!("<a id=\"{}\"></a>\n\n", anchor(path, item)));
+        }
+        out.push_str(&format!("{} {} `{}`", "#".repeat(level), item.r#type, item.name));
+        if !item.visibility.badge().is_empty() {
+            out.push_str(&format!(" ({})", item.visibility.badge()));
+        }
+        for badge in item.information.get_attributes().unwrap_or_default().badges() {
+            out.push_str(&format!(" `{}`", badge));
+        }
+        out.push_str("\n\n");
+
+        if let Some(signature) = item.information.get_signature() {
+            out.push_str(&format!("```noir\n{}\n```\n\n", signature));
+        }
+        if let Some(note) = item.information.get_deprecated() {
+            out.push_str(&deprecation_notice(&note));
+        }
+        if !item.doc.trim().is_empty() {
+            out.push_str(&format!("{}\n\n", shown_doc(&item.doc)));
+        }
+        if let Some(additional_doc) = item.information.get_additional_doc() {
+            if !additional_doc.trim().is_empty() {
+                out.push_str(&format!("{}\n\n", shown_doc(&additional_doc)));
+            }
+        }
+        out.push_str(&self.defined_in(&item.span));
+
+        // ids of the fields and methods are prefixed with the item section in a single file
+        let prefix = if self.single_file { format!("{}/", anchor(path, item)) } else { String::new() };
+
+        if let Some(fields) = item.information.get_fields().filter(|fields| !fields.is_empty()) {
+            out.push_str(&format!("{} Fields\n\n", "#".repeat(level + 1)));
+            for field in fields.iter() {
+                out.push_str(&format!("<a id=\"{}{}\"></a>\n\n`{}{}: {}`\n\n", prefix, field.anchor(), field.visibility.keyword(), field.name, field.r#type));
+                if !field.doc.trim().is_empty() {
+                    out.push_str(&format!("{}\n\n", shown_doc(&field.doc)));
+                }
+            }
+        }
+
+        if let Some(required_methods) = item.information.get_required_methods() {
+            functions(out, level + 1, "Required methods", &required_methods, &prefix);
+        }
+        if let Some(provided_methods) = item.information.get_provided_methods() {
+            functions(out, level + 1, "Provided methods", &provided_methods, &prefix);
+        }
+        if let Some(implementations) = item.information.get_implementations() {
+            if item.r#type == Type::Trait {
+                self.implementations(out, level + 1, "Implementors", implementations.iter(), &prefix);
+            }
+            else {
+                self.implementations(out, level + 1, "Implementations", implementations.iter().filter(|implementation| !implementation.is_trait_impl()), &prefix);
+                self.implementations(out, level + 1, "Trait Implementations", implementations.iter().filter(|implementation| implementation.is_trait_impl()), &prefix);
+            }
+        }
+    }
+
+    fn implementations<'a>(&self, out: &mut String, level: usize, title: &str, implementations: impl Iterator<Item = &'a Implementation>, prefix: &str) {
+        let implementations = implementations.collect::<Vec<_>>();
+        if implementations.is_empty() {
+            return;
+        }
+
+        out.push_str(&format!("{} {}\n\n", "#".repeat(level), title));
+        for implementation in implementations.iter() {
+            out.push_str(&format!("{} `{}`\n\n", "#".repeat(level + 1), implementation.signature));
+            out.push_str(&self.defined_in(&implementation.span));
+            for function in implementation.functions.iter() {
+                function_entry(out, function, prefix);
+            }
+        }
+    }
+
+    fn link(&self, path: &[String], item: &Output) -> String {
+        if self.single_file {
+            format!("#{}", anchor(path, item))
+        }
+        else {
+            item.r#type.page_file(&item.name, "md")
+        }
+    }
+
+    fn defined_in(&self, span: &SourceSpan) -> String {
+        format!("*Defined in `{}` at lines {}-{}.*\n\n", span.relative_path(self.src_root).join("/"), span.start_line, span.end_line)
+    }
+}
+
+/// the non-empty sections of the module tables, documented tests get their own section after the other items
+pub(crate) fn module_sections(items: &[Output]) -> Vec<ItemSection> {
+    SECTIONS.iter()
+        .map(|(r#type, title)| ItemSection { title: title.to_string(), items: items.iter().filter(|item| item.r#type == *r#type && !item.is_test()).cloned().collect() })
+        .chain(iter::once(ItemSection { title: "Tests".to_string(), items: items.iter().filter(|item| item.is_test()).cloned().collect() }))
+        .filter(|section| !section.items.is_empty())
+        .collect()
+}
+
+fn functions(out: &mut String, level: usize, title: &str, functions: &[Function], prefix: &str) {
+    if functions.is_empty() {
+        return;
+    }
+
+    out.push_str(&format!("{} {}\n\n", "#".repeat(level), title));
+    for function in functions.iter() {
+        function_entry(out, function, prefix);
+    }
+}
+
+fn function_entry(out: &mut String, function: &Function, prefix: &str) {
+    out.push_str(&format!("<a id=\"{}{}\"></a>\n\n```noir\n{}\n```\n\n", prefix, function.anchor(), function.signature));
+    let badges = function.attributes.badges();
+    if !badges.is_empty() {
+        out.push_str(&format!("{}\n\n", badges.iter().map(|badge| format!("`{}`", badge)).collect::<Vec<_>>().join(" ")));
+    }
+    if let Some(note) = &function.attributes.deprecated {
+        out.push_str(&deprecation_notice(note));
+    }
+    if !function.doc.trim().is_empty() {
+        out.push_str(&format!("{}\n\n", shown_doc(&function.doc)));
+    }
+}
+
+/// quote with the note of a `#[deprecated]` attribute
+fn deprecation_notice(note: &str) -> String {
+    let note = note.trim();
+    if note.is_empty() {
+        return "> **Deprecated**\n\n".to_string();
+    }
+
+    let quoted = note.lines().map(|line| format!("> {}", line)).collect::<Vec<_>>().join("\n");
+    format!("> **Deprecated**: {}\n\n", quoted.trim_start_matches("> "))
+}
+
+/// id of the item section in a single markdown file
+fn anchor(path: &[String], item: &Output) -> String {
+    match item.r#type {
+        Type::Module | Type::Contract => format!("{}/{}", path.join("/"), item.name),
+        _ => format!("{}/{}", path.join("/"), item.r#type.page_file(&item.name, "md").trim_end_matches(".md")),
+    }
+}
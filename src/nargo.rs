@@ -0,0 +1,123 @@
+use std::{fmt, fs, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+const MANIFEST_NAME: &str = "Nargo.toml";
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Deserialize)]
+pub(crate) enum PackageType {
+    #[serde(rename = "bin")]
+    Binary,
+    #[serde(rename = "lib")]
+    Library,
+    #[serde(rename = "contract")]
+    Contract,
+}
+
+impl fmt::Display for PackageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageType::Binary => write!(f, "bin"),
+            PackageType::Library => write!(f, "lib"),
+            PackageType::Contract => write!(f, "contract"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NargoToml {
+    package: Option<PackageConfig>,
+    workspace: Option<WorkspaceConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageConfig {
+    name: String,
+    r#type: Option<PackageType>,
+    entry: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceConfig {
+    members: Vec<PathBuf>,
+}
+
+/// a Nargo package the documentation is generated for
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Package {
+    pub(crate) name: String,
+    pub(crate) r#type: PackageType,
+    pub(crate) root_dir: PathBuf,
+    pub(crate) entry_path: PathBuf,
+}
+
+impl Package {
+    fn from_config(config: PackageConfig, root_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let r#type = config.r#type.unwrap_or(PackageType::Binary);
+
+        let entry_path = match config.entry {
+            Some(entry) => root_dir.join(entry),
+            None => match r#type {
+                PackageType::Library => root_dir.join("src").join("lib.nr"),
+                PackageType::Binary | PackageType::Contract => root_dir.join("src").join("main.nr"),
+            },
+        };
+
+        if !entry_path.is_file() {
+            return Err(format!("entry file {} of package `{}` does not exist", entry_path.display(), config.name).into());
+        }
+
+        Ok(Package { name: config.name, r#type, root_dir: root_dir.to_path_buf(), entry_path })
+    }
+}
+
+/// a set of packages described by a single Nargo.toml
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Workspace {
+    pub(crate) root_dir: PathBuf,
+    pub(crate) members: Vec<Package>,
+    pub(crate) is_workspace: bool,
+}
+
+/// searches the program directory and its ancestors for a Nargo.toml
+pub(crate) fn find_manifest(program_dir: &Path) -> Option<PathBuf> {
+    program_dir.ancestors().map(|dir| dir.join(MANIFEST_NAME)).find(|manifest| manifest.is_file())
+}
+
+/// reads the Nargo.toml of a package or a workspace
+pub(crate) fn resolve_workspace(program_dir: &Path) -> Result<Workspace, Box<dyn std::error::Error>> {
+    let manifest = find_manifest(program_dir)
+        .ok_or_else(|| format!("could not find {} in {} or any parent directory", MANIFEST_NAME, program_dir.display()))?;
+    let root_dir = manifest.parent().unwrap_or(program_dir).to_path_buf();
+
+    match read_manifest(&manifest)? {
+        NargoToml { workspace: Some(workspace), .. } => {
+            let mut members = Vec::new();
+
+            for member in workspace.members {
+                let member_dir = root_dir.join(member);
+                let member_manifest = member_dir.join(MANIFEST_NAME);
+
+                match read_manifest(&member_manifest)?.package {
+                    Some(config) => members.push(Package::from_config(config, &member_dir)?),
+                    None => return Err(format!("{} does not describe a package", member_manifest.display()).into()),
+                }
+            }
+
+            Ok(Workspace { root_dir, members, is_workspace: true })
+        }
+        NargoToml { package: Some(config), .. } => {
+            let package = Package::from_config(config, &root_dir)?;
+
+            Ok(Workspace { root_dir, members: vec![package], is_workspace: false })
+        }
+        _ => Err(format!("{} describes neither a package nor a workspace", manifest.display()).into()),
+    }
+}
+
+fn read_manifest(manifest: &Path) -> Result<NargoToml, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(manifest)
+        .map_err(|err| format!("failed to read {}: {}", manifest.display(), err))?;
+
+    toml::from_str(&contents).map_err(|err| format!("failed to parse {}: {}", manifest.display(), err).into())
+}
@@ -1,15 +1,20 @@
 use std::fmt;
 
-use noirc_frontend::token::{Token, Keyword, DocComments, SpannedToken};
+use noirc_frontend::{parser::{Item, ItemKind}, token::SpannedToken, Ident};
 
-use crate::{Function, Implementation, doc, fn_signature, struct_signature, additional_doc, skip_impl_block, trait_info, get_module_content, outer_doc};
+use crate::{contract_visibility, link_implementations, Field, FunctionAttributes, deprecation, secondary_attributes, ItemVisibility, visibility, fields_signature, Function, Implementation, Import, ModuleLocation, imports, SourceFile, SourceSpan, doc, fn_signature, struct_signature, struct_fields, pattern_name, global_type, global_signature, type_alias_signature, additional_doc, trait_info, get_module_content, outer_doc, doc_text};
 
+// `TypeAlias` is named after the `type` keyword of the item
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub(crate) enum Type {
     Function,
     Module,
     Struct,
     Trait,
+    Global,
+    TypeAlias,
+    Contract,
     OuterComment,
 }
 
@@ -20,23 +25,56 @@ impl fmt::Display for Type {
             Type::Module => write!(f, "Module"),
             Type::Struct => write!(f, "Struct"),
             Type::Trait => write!(f, "Trait"),
+            Type::Global => write!(f, "Global"),
+            Type::TypeAlias => write!(f, "Type alias"),
+            Type::Contract => write!(f, "Contract"),
             Type::OuterComment => write!(f, "OuterComment"),
         }
     }
 }
 
+impl Type {
+    /// name of the html page documenting an item of this type
+    /// modules and contracts are documented by the index page of their own directory
+    pub(crate) fn page_name(&self, name: &str) -> String {
+        self.page_file(name, "html")
+    }
+
+    /// name of the page documenting an item of this type with the given file extension
+    pub(crate) fn page_file(&self, name: &str, extension: &str) -> String {
+        match self {
+            Type::Function => format!("fn.{}.{}", name, extension),
+            Type::Struct => format!("struct.{}.{}", name, extension),
+            Type::Trait => format!("trait.{}.{}", name, extension),
+            Type::Global => format!("global.{}.{}", name, extension),
+            Type::TypeAlias => format!("type.{}.{}", name, extension),
+            Type::Module | Type::Contract => format!("{}/index.{}", name, extension),
+            Type::OuterComment => String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub(crate) enum Info {
     Function{
         signature: String,
+        attributes: FunctionAttributes,
     },
     Module{
         content: Vec<Output>,
+        imports: Vec<Import>,
+        /// impl blocks written in the module, moved to their structs and traits once the whole crate is read
+        impls: Vec<Implementation>,
     },
     Struct {
         signature: String,
         additional_doc: String,
+        fields: Vec<Field>,
         implementations: Vec<Implementation>,
+        /// the attributes as written, e.g. `#[event]` or `#[storage]`
+        attributes: Vec<String>,
+        /// the note of a `#[deprecated]` attribute, empty if it has none
+        deprecated: Option<String>,
     },
     Trait {
         signature: String,
@@ -45,13 +83,23 @@ pub(crate) enum Info {
         provided_methods: Vec<Function>,
         implementations: Vec<Implementation>,
     },
+    Global {
+        signature: String,
+        /// empty if the type is not written
+        r#type: String,
+        value: String,
+    },
+    TypeAlias {
+        signature: String,
+        aliased_type: String,
+    },
     Blanc,
 }
 
 impl Info {
     pub(crate) fn get_signature(&self) -> Option<String> {
         match self {
-            Info::Function { signature } => {
+            Info::Function { signature, .. } => {
                 Some(signature.to_string())
             },
             Info::Struct { signature, .. } => {
@@ -60,6 +108,12 @@ impl Info {
             Info::Trait { signature, .. } => {
                 Some(signature.to_string())
             }
+            Info::Global { signature, .. } => {
+                Some(signature.to_string())
+            }
+            Info::TypeAlias { signature, .. } => {
+                Some(signature.to_string())
+            }
             _ => {
                 None
             }
@@ -94,6 +148,17 @@ impl Info {
         }
     }
 
+    pub(crate) fn get_fields(&self) -> Option<Vec<Field>> {
+        match self {
+            Info::Struct { fields, .. } => {
+                Some(fields.clone())
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
     pub(crate) fn get_required_methods(&self) -> Option<Vec<Function>> {
         match self {
             Info::Trait { required_methods, .. } => {
@@ -116,9 +181,45 @@ impl Info {
         }
     }
 
+    pub(crate) fn get_attributes(&self) -> Option<FunctionAttributes> {
+        match self {
+            Info::Function { attributes, .. } => {
+                Some(attributes.clone())
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
+    pub(crate) fn get_struct_attributes(&self) -> Option<Vec<String>> {
+        match self {
+            Info::Struct { attributes, .. } => {
+                Some(attributes.clone())
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
+    pub(crate) fn get_deprecated(&self) -> Option<String> {
+        match self {
+            Info::Function { attributes, .. } => {
+                attributes.deprecated.clone()
+            }
+            Info::Struct { deprecated, .. } => {
+                deprecated.clone()
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
     pub(crate) fn get_content(&self) -> Option<Vec<Output>> {
         match self {
-            Info::Module { content } => {
+            Info::Module { content, .. } => {
                 Some(content.clone())
             }
             _ => {
@@ -134,117 +235,180 @@ pub(crate) struct Output {
     pub(crate) name: String,
     pub(crate) doc: String,
     pub(crate) information: Info,
+    pub(crate) visibility: ItemVisibility,
+    pub(crate) span: SourceSpan,
 }
 
 impl Output {
-    pub(crate) fn to_output(input: Vec<SpannedToken>) -> Vec<Self> {
+    /// link to the item page relative to the page of its module
+    pub(crate) fn link(&self) -> String {
+        self.r#type.page_name(&self.name)
+    }
+
+    /// functions marked with `#[test]`
+    pub(crate) fn is_test(&self) -> bool {
+        matches!(&self.information, Info::Function { attributes, .. } if attributes.is_test)
+    }
+
+    /// functions and structs marked with `#[deprecated]`
+    pub(crate) fn is_deprecated(&self) -> bool {
+        self.information.get_deprecated().is_some()
+    }
+
+    /// extracts the documented items of the crate with the given root file
+    /// the implementations are attached to their structs and traits wherever in the crate they are written
+    pub(crate) fn to_output(source: &SourceFile, location: &ModuleLocation) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        let mut res = Output::from_file(source, location)?;
+        let impls = Implementation::from_items(&source.module.items, source);
+
+        link_implementations(&mut res, &imports(&source.module.items), impls);
+
+        Ok(res)
+    }
+
+    /// extracts the documented items of the parsed source file
+    pub(crate) fn from_file(source: &SourceFile, location: &ModuleLocation) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        Output::from_items(&source.module.items, source, location, 0, source.text.len() as u32)
+    }
+
+    /// extracts the documented items of a module written between `start` and `end` of the source file
+    fn from_items(items: &[Item], source: &SourceFile, location: &ModuleLocation, start: u32, end: u32) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        let tokens = &source.tokens;
         let mut res = Vec::new();
-        let tokens = input.into_iter().map(|x| x.into_token()).collect::<Vec<_>>();
-        let mut is_first = true;
-        let mut skip_count = 0;
-
-        for i in 0..tokens.len() {
-            if skip_count > 0 {
-                skip_count -= 1;
-                continue;
-            }
-            let out = match &tokens[i] {
-                Token::Keyword(Keyword::Fn) => {
-                    let r#type = Type::Function;
-                    let name = match &tokens[i + 1] {
-                        Token::Ident(idn) => {
-                            idn.clone()
-                        }
-                        _ => {continue;}
-                    };
-                    let doc = doc(&tokens, i);
-                    let sign = fn_signature(&tokens, i);
-                    
-                    Output{r#type, name, doc, information: Info::Function { signature: sign }}
-                }
-                Token::Keyword(Keyword::Struct) => {
-                    let r#type = Type::Struct;
-                    let name = match &tokens[i + 1] {
-                        Token::Ident(idn) => {
-                            idn.clone()
-                        }
-                        _ => {continue;}
-                    };
-                    let doc = doc(&tokens, i);
-                    let sign = struct_signature(&tokens, i - 1);
-                    let ad_doc = additional_doc(&tokens, i);
 
-                    Output{r#type, name: name.clone(), doc, information: Info::Struct { signature: sign, additional_doc: ad_doc, implementations: Implementation::get_implementations(&tokens, i, name) }}
-                }
-                Token::Keyword(Keyword::Trait) => {
-                    skip_count = skip_impl_block(&tokens, i);
-
-                    let r#type = Type::Trait;
-                    let name = match &tokens[i + 1] {
-                        Token::Ident(idn) => {
-                            idn.clone()
-                        }
-                        _ => {continue;}
-                    };
-                    let doc = doc(&tokens, i);
+        for item in items.iter() {
+            let out = match &item.kind {
+                ItemKind::Function(function) => {
+                    let name = function.def.name.clone();
 
-                    let ad_doc = additional_doc(&tokens, i);
-                    let impls = Implementation::get_implementations(&tokens, i, name.clone());
-                    let info = trait_info(&tokens, i);
+                    Output{r#type: Type::Function, name: name.to_string(), doc: doc(tokens, &name), information: Info::Function { signature: fn_signature(&function.def), attributes: FunctionAttributes::new(&function.def) }, visibility: visibility(tokens, &name), span: source.item_span(item.span, &name)}
+                }
+                ItemKind::Struct(structure) => {
+                    let name = structure.name.clone();
+                    let fields = struct_fields(source, structure);
+                    let sign = struct_signature(structure, &fields);
+                    let ad_doc = additional_doc(tokens, &name);
 
-                    Output{r#type, name, doc, information: Info::Trait { signature: info.0, additional_doc: ad_doc, required_methods: info.1, provided_methods: info.2, implementations: impls }}
+                    Output{r#type: Type::Struct, name: name.to_string(), doc: doc(tokens, &name), information: Info::Struct { signature: sign, additional_doc: ad_doc, fields, implementations: Vec::new(), attributes: secondary_attributes(&structure.attributes), deprecated: deprecation(&structure.attributes) }, visibility: visibility(tokens, &name), span: source.item_span(item.span, &name)}
                 }
-                Token::Keyword(Keyword::Mod) => {
-                    if tokens[i + 2] == Token::LeftBrace {
-                        skip_count = skip_impl_block(&tokens, i);
-                    }
+                ItemKind::Trait(r#trait) => {
+                    let name = r#trait.name.clone();
+                    let ad_doc = additional_doc(tokens, &name);
+                    let info = trait_info(source, r#trait);
 
-                    let r#type = Type::Module;
-                    let name = match &tokens[i + 1] {
-                        Token::Ident(idn) => {
-                            idn.clone()
-                        }
-                        _ => {continue;}
-                    };
-                    let doc = doc(&tokens, i);
-                    let content = get_module_content(&tokens, i);
+                    Output{r#type: Type::Trait, name: name.to_string(), doc: doc(tokens, &name), information: Info::Trait { signature: info.0, additional_doc: ad_doc, required_methods: info.1, provided_methods: info.2, implementations: Vec::new() }, visibility: visibility(tokens, &name), span: source.item_span(item.span, &name)}
+                }
+                ItemKind::ModuleDecl(name) => {
+                    let (information, span) = get_module_content(source, location, name)?;
+                    // the doc comment is written above the declaration, not in the module file
+                    let span = SourceSpan { doc: source.doc_span(name, false), ..span };
 
-                    Output{r#type, name, doc, information: Info::Module { content }}
+                    Output{r#type: Type::Module, name: name.to_string(), doc: doc(tokens, name), information, visibility: implicit_visibility(tokens, name), span}
                 }
-                Token::DocComment(DocComments::Outer(_)) => {
-                    let r#type = Type::OuterComment;
-                    let name = "".to_string();
+                ItemKind::Submodules(module) => {
+                    let name = module.name.clone();
+                    let content = Output::from_items(&module.contents.items, source, &location.inline_module(&name.0.contents), item.span.start(), item.span.end())?;
+                    let imports = imports(&module.contents.items);
+                    let impls = Implementation::from_items(&module.contents.items, source);
 
-                    let res = outer_doc(&tokens, i);
+                    // contracts hold their items the same way as modules
+                    let (r#type, content) = if module.is_contract { (Type::Contract, contract_visibility(content)) } else { (Type::Module, content) };
 
-                    let doc = if is_first {
-                        is_first = false;
-                        res.0
-                    }
-                    else {
-                        if res.1 == i {
-                            is_first = true;
-                        }
-                        "".to_string()
+                    Output{r#type, name: name.to_string(), doc: doc(tokens, &name), information: Info::Module { content, imports, impls }, visibility: implicit_visibility(tokens, &name), span: source.item_span(item.span, &name)}
+                }
+                ItemKind::Global(global) => {
+                    let Some(name) = pattern_name(&global.pattern) else {
+                        continue;
                     };
-                    
+                    let information = Info::Global { signature: global_signature(tokens, &name, global), r#type: global_type(global), value: global.expression.to_string() };
 
-                    Output{r#type, name, doc, information: Info::Blanc}
+                    Output{r#type: Type::Global, name: name.to_string(), doc: doc(tokens, &name), information, visibility: implicit_visibility(tokens, &name), span: source.item_span(item.span, &name)}
                 }
-                Token::Keyword(Keyword::Impl) => {
-                    skip_count = skip_impl_block(&tokens, i);
+                ItemKind::TypeAlias(alias) => {
+                    let name = alias.name.clone();
+                    let information = Info::TypeAlias { signature: type_alias_signature(tokens, alias), aliased_type: alias.typ.to_string() };
+
+                    Output{r#type: Type::TypeAlias, name: name.to_string(), doc: doc(tokens, &name), information, visibility: implicit_visibility(tokens, &name), span: source.item_span(item.span, &name)}
+                }
+                ItemKind::Impl(_) | ItemKind::TraitImpl(_) | ItemKind::Import(_) => {
                     continue;
                 }
-                _ => {continue;}
             };
 
-            res.push(out);
+            res.push((item.span.start(), out));
+        }
+
+        let item_spans = items.iter().map(|item| item.span).collect::<Vec<_>>();
+        for run in outer_doc(tokens, &item_spans, start, end) {
+            let offset = run[0].to_span().start();
+            let line = source.line(offset);
+            let span = SourceSpan { doc: source.comment_span(&run), ..SourceSpan::new(source.path.clone(), line, line) };
+            let doc = doc_text(&run);
+
+            res.push((offset, Output{r#type: Type::OuterComment, name: "".to_string(), doc, information: Info::Blanc, visibility: ItemVisibility::Public, span}));
         }
 
-        res
+        res.sort_by_key(|(offset, _)| *offset);
+
+        Ok(res.into_iter().map(|(_, out)| out).collect())
     }
 }
 
+/// modules, globals and type aliases without `pub` are public, Noir has no private ones
+fn implicit_visibility(tokens: &[SpannedToken], name: &Ident) -> ItemVisibility {
+    match visibility(tokens, name) {
+        ItemVisibility::Private => ItemVisibility::Public,
+        visibility => visibility,
+    }
+}
+
+/// keeps the items reachable from the crate root through `pub` items
+/// the fields and methods that are not `pub` are removed as well
+pub(crate) fn public_api(items: Vec<Output>) -> Vec<Output> {
+    let is_public = |visibility: ItemVisibility| visibility == ItemVisibility::Public;
+
+    items.into_iter()
+        .filter(|item| item.r#type == Type::OuterComment || is_public(item.visibility))
+        .map(|mut item| {
+            match &mut item.information {
+                Info::Module { content, .. } => {
+                    *content = public_api(std::mem::take(content));
+                }
+                Info::Struct { signature, fields, implementations, .. } => {
+                    let private_fields = fields.iter().any(|field| !is_public(field.visibility));
+                    fields.retain(|field| is_public(field.visibility));
+
+                    let header = signature.split(" {").next().unwrap_or_default().to_string();
+                    *signature = fields_signature(&header, fields, private_fields);
+
+                    for implementation in implementations.iter_mut() {
+                        implementation.functions.retain(|function| is_public(function.visibility));
+                    }
+                    // inherent implementations with private methods only are not shown
+                    implementations.retain(|implementation| implementation.trait_name.is_some() || !implementation.functions.is_empty());
+                }
+                Info::Function { .. } | Info::Trait { .. } | Info::Global { .. } | Info::TypeAlias { .. } | Info::Blanc => {}
+            }
+
+            item
+        })
+        .collect()
+}
+
+/// removes the test functions of the modules
+pub(crate) fn without_tests(items: Vec<Output>) -> Vec<Output> {
+    items.into_iter()
+        .filter(|item| !item.is_test())
+        .map(|mut item| {
+            if let Info::Module { content, .. } = &mut item.information {
+                *content = without_tests(std::mem::take(content));
+            }
+
+            item
+        })
+        .collect()
+}
+
 impl fmt::Display for Output {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Type: {:?}\n", self.r#type)?;
@@ -1,18 +1,143 @@
-use std::{fs::{File, self}, io::Write, path::Path};
+use std::{fs::{self, File}, io::Write, path::{Path, PathBuf}};
 
 use askama::Template;
-use noirc_frontend::token::{Token, Keyword};
+use noirc_frontend::{hir::resolution::errors::Span, parser::{Item, ItemKind}, NoirFunction, NoirTraitImpl, TraitImplItem, TypeImpl};
 
-use crate::{Type, Output, fn_signature, doc, Code, get_text};
+use crate::{Type, Output, Package, SourceFile, SourceSpan, fn_signature, doc, generics, type_path, where_clause, Code, get_text, Field, ItemVisibility, visibility, method_anchor, FunctionAttributes, ItemSection, contract_sections, module_sections};
 
-fn generate_code_page(input_file: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let codelines = get_text(input_file)?;
+/// filters used by the templates to insert rendered doc comments
+mod filters {
+    pub(crate) fn doc_html(doc: &str) -> askama::Result<String> {
+        Ok(crate::render_html(doc))
+    }
+
+    pub(crate) fn summary_html(doc: &str) -> askama::Result<String> {
+        Ok(crate::summary_html(doc))
+    }
+}
+
+/// directory of the output with the styles, scripts and icons shared by all pages
+pub(crate) const ASSETS_DIR: &str = "assets";
+
+/// files of the asset bundle, embedded into the generator
+const ASSETS: [(&str, &str); 6] = [
+    ("style.css", include_str!("../assets/style.css")),
+    ("search.js", include_str!("../assets/search.js")),
+    ("search_results.js", include_str!("../assets/search_results.js")),
+    ("source.js", include_str!("../assets/source.js")),
+    ("search.svg", include_str!("../assets/search.svg")),
+    ("favicon.svg", include_str!("../assets/favicon.svg")),
+];
+
+/// writes the asset bundle into the output directory
+/// pages link to it with relative paths, so the output can be moved or served from anywhere
+pub(crate) fn generate_assets(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let assets_dir = output_dir.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir)?;
+
+    for (name, content) in ASSETS.iter() {
+        let mut file = File::create(assets_dir.join(name))?;
+        file.write_all(content.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// search page in the crate documentation directory
+pub(crate) const SEARCH_PAGE: &str = "search.html";
+
+/// location of the generated pages of a module
+#[derive(Debug, Clone)]
+pub(crate) struct ModulePage {
+    /// directory with the documentation of the crate
+    pub(crate) crate_dir: PathBuf,
+    /// directory with the source files of the crate
+    pub(crate) src_root: PathBuf,
+    /// names of the modules from the crate root to this module
+    pub(crate) path: Vec<String>,
+}
+
+impl ModulePage {
+    pub(crate) fn crate_root(crate_dir: PathBuf, src_root: PathBuf) -> Self {
+        ModulePage { crate_dir, src_root, path: Vec::new() }
+    }
+
+    fn child(&self, name: &str) -> Self {
+        let mut path = self.path.clone();
+        path.push(name.to_string());
+
+        ModulePage { crate_dir: self.crate_dir.clone(), src_root: self.src_root.clone(), path }
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.path.iter().fold(self.crate_dir.clone(), |dir, name| dir.join(name))
+    }
+
+    /// link to the item source relative to the module directory
+    fn source_link(&self, span: &SourceSpan) -> String {
+        format!("{}{}", "../".repeat(self.path.len()), span.link(&self.src_root))
+    }
+
+    /// link to the page of an item with the given path from the crate root relative to the module directory
+    fn item_link(&self, path: &[String], r#type: Type) -> String {
+        let Some((name, module)) = path.split_last() else {
+            return String::new();
+        };
+        let down = module.iter().map(|name| format!("{}/", name)).collect::<String>();
+
+        format!("{}{}{}", "../".repeat(self.path.len()), down, r#type.page_name(name))
+    }
+
+    /// the implementations with the links to their sources and to the pages of the implemented structs
+    fn implementations(&self, implementations: Vec<Implementation>) -> Vec<Implementation> {
+        implementations.into_iter().map(|mut implementation| {
+            implementation.source_link = self.source_link(&implementation.span);
+            implementation.self_link = self.item_link(&implementation.self_path, Type::Struct);
+            implementation.functions = self.methods(implementation.functions);
+            implementation
+        }).collect()
+    }
+
+    /// the methods with the links to their sources
+    fn methods(&self, methods: Vec<Function>) -> Vec<Function> {
+        methods.into_iter().map(|mut method| {
+            method.source_link = self.source_link(&method.span);
+            method
+        }).collect()
+    }
+
+    /// link to the crate search page relative to the module directory
+    fn search_link(&self) -> String {
+        format!("{}{}", "../".repeat(self.path.len()), SEARCH_PAGE)
+    }
+
+    /// link to the asset bundle relative to the module directory
+    /// the bundle is placed next to the crate directory
+    fn assets_link(&self) -> String {
+        format!("{}../{}", "../".repeat(self.path.len()), ASSETS_DIR)
+    }
+}
+
+/// generates the page with the source file the span points at
+/// the page is placed under `src` in the crate documentation directory, mirroring the source tree
+fn generate_code_page(span: &SourceSpan, page: &ModulePage) -> Result<(), Box<dyn std::error::Error>> {
+    let codelines = get_text(&span.file)?;
+
+    let source_page = span.source_page(&page.src_root);
+    let filename = source_page[1..].join("/").trim_end_matches(".html").to_string();
+
+    let assets = format!("{}{}", "../".repeat(source_page.len()), ASSETS_DIR);
 
-    let code = Code{ codelines };
+    let code = Code{ filename, codelines, assets };
 
     let rendered_html = code.render().unwrap();
 
-    let mut file = File::create("generated_doc/codepage.html")?;
+    let output_file_name = source_page.iter().fold(page.crate_dir.clone(), |dir, name| dir.join(name));
+    if let Some(dir) = output_file_name.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = File::create(output_file_name)?;
     file.write_all(rendered_html.as_bytes())?;
 
     Ok(())
@@ -25,15 +150,32 @@ pub(crate) struct Function {
     pub(crate) doc: String, 
     pub(crate) signature: String,
     pub(crate) is_method: bool,
+    pub(crate) visibility: ItemVisibility,
+    pub(crate) attributes: FunctionAttributes,
+    pub(crate) span: SourceSpan,
+    pub(crate) source_link: String,
+    pub(crate) assets: String,
+    /// number of the method among the methods of the same name on the struct page, 0 for the first inherent one
+    pub(crate) anchor_index: usize,
+}
+
+impl Function {
+    /// id of the method section on the page of its struct or trait
+    pub(crate) fn anchor(&self) -> String {
+        match self.anchor_index {
+            0 => method_anchor(&self.name),
+            index => format!("{}-{}", method_anchor(&self.name), index),
+        }
+    }
 }
 
-fn generate_function_pages(func: Function) -> Result<(), Box<dyn std::error::Error>> {
+fn generate_function_pages(func: Function, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
     if func.is_method {
         return Ok(());
     }
     let rendered_html = func.render().unwrap();
 
-    let output_file_name = format!("generated_doc/{}.html", func.name);
+    let output_file_name = output_dir.join(Type::Function.page_name(&func.name));
 
     let mut file = File::create(output_file_name)?;
     file.write_all(rendered_html.as_bytes())?;
@@ -48,101 +190,136 @@ pub(crate) struct Structure {
     doc: String, 
     additional_doc: String,
     signature: String,
+    fields: Vec<Field>,
     implementations: Vec<Implementation>,
+    visibility: ItemVisibility,
+    attributes: Vec<String>,
+    deprecated: Option<String>,
+    source_link: String,
+    assets: String,
+}
+
+impl Structure {
+    pub(crate) fn new(item: &Output, page: &ModulePage, assets: &str) -> Self {
+        Structure { 
+            name: item.name.clone(), 
+            doc: item.doc.clone(), 
+            additional_doc: item.information.get_additional_doc().unwrap(),
+            signature: item.information.get_signature().unwrap(), 
+            fields: item.information.get_fields().unwrap(),
+            implementations: page.implementations(item.information.get_implementations().unwrap()),
+            visibility: item.visibility,
+            attributes: item.information.get_struct_attributes().unwrap(),
+            deprecated: item.information.get_deprecated(),
+            source_link: page.source_link(&item.span),
+            assets: assets.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub(crate) struct Implementation {
-    signature: String,
-    functions: Vec<Function>,
+    pub(crate) signature: String,
+    /// name of the implemented trait, `None` for inherent implementations
+    pub(crate) trait_name: Option<String>,
+    /// path of the implemented type as written in the impl, e.g. `shapes::Point`
+    pub(crate) self_type: String,
+    /// path of the implemented struct from the crate root, empty if the type is not a struct of the crate
+    pub(crate) self_path: Vec<String>,
+    pub(crate) functions: Vec<Function>,
+    pub(crate) span: SourceSpan,
+    pub(crate) source_link: String,
+    /// link to the page of the implemented struct, empty if it has no page
+    pub(crate) self_link: String,
 }
 
 impl Implementation {
-    pub(crate) fn get_implementations(tokens: &[Token], index: usize, orig_name: String) -> Vec<Implementation> {
-        let mut res = Vec::new();
-        let mut functions = Vec::new();
-        let mut signature = String::new();
-        let mut right_impl = false;
-        let mut i = index;
-        let mut brace_counter = 0;
-
-        while i < tokens.len() {
-            match tokens[i] {
-                Token::Keyword(Keyword::Impl) => {
-                    loop {
-                        match &tokens[i] {
-                            Token::Ident(name) => {
-                                if name == &orig_name {
-                                    right_impl = true;
-                                }
-                                signature.push_str(&tokens[i].to_string());
-                                signature.push_str(" ");
-                                i +=1;
-                            }
-                            Token::LeftBrace => {
-                                if !right_impl {
-                                    signature = "".to_string();
-                                    break;
-                                }
-                                else {
-                                    brace_counter += 1;
-                                    i += 1;
-                                    while brace_counter != 0 {
-                                        match &tokens[i] {
-                                            Token::Keyword(Keyword::Fn) => {
-                                                let name = match &tokens[i + 1] {
-                                                    Token::Ident(idn) => {
-                                                        idn.clone()
-                                                    }
-                                                    _ => {continue;}
-                                                };
-                                                let doc = doc(&tokens, i);
-                                                let sign = fn_signature(&tokens, i);
-                                                
-                                                functions.push(Function{ name, doc, signature: sign, is_method: true });
-
-                                                i += 1;
-                                            }
-                                            Token::LeftBrace => {
-                                                i += 1;
-                                                brace_counter += 1;
-                                            }
-                                            Token::RightBrace => {
-                                                i += 1;
-                                                brace_counter -= 1;
-                                            }
-                                            _ => {
-                                                i += 1;
-                                            }
-                                        }
-                                    }
-
-                                    res.push(Implementation { signature: signature.clone(), functions: functions.clone() });
-                                    signature = "".to_string();
-                                    functions = vec![];
-                                    break;
-                                }
-                            }
-                            _ => {
-                                signature.push_str(&tokens[i].to_string());
-                                signature.push_str(" ");
-                                i +=1;
-                            }
-                        }
-                    }
-                }
-                _ => {i += 1;}
-            }
+    /// collects the inherent and trait implementations written among the module items
+    /// they are moved to the types and traits they implement once the whole crate is read
+    pub(crate) fn from_items(items: &[Item], source: &SourceFile) -> Vec<Implementation> {
+        items.iter().filter_map(|item| match &item.kind {
+            ItemKind::Impl(type_impl) => Some(Implementation::inherent(type_impl, source, item.span)),
+            ItemKind::TraitImpl(trait_impl) => Some(Implementation::trait_impl(trait_impl, source, item.span)),
+            _ => None,
+        }).collect()
+    }
+
+    /// trait implementations are listed apart from the inherent ones on the struct pages
+    pub(crate) fn is_trait_impl(&self) -> bool {
+        self.trait_name.is_some()
+    }
+
+    fn inherent(type_impl: &TypeImpl, source: &SourceFile, span: Span) -> Implementation {
+        let signature = format!("impl{} {}", generics(&type_impl.generics), type_impl.object_type);
+        let functions = type_impl.methods.iter().map(|method| Implementation::method(method, source, visibility(&source.tokens, &method.def.name))).collect();
+
+        Implementation {
+            signature,
+            trait_name: None,
+            self_type: type_path(&type_impl.object_type).unwrap_or_default(),
+            self_path: Vec::new(),
+            functions,
+            span: source.span(span),
+            source_link: String::new(),
+            self_link: String::new(),
+        }
+    }
+
+    fn trait_impl(trait_impl: &NoirTraitImpl, source: &SourceFile, span: Span) -> Implementation {
+        let trait_generics = if trait_impl.trait_generics.is_empty() {
+            String::new()
         }
+        else {
+            let trait_generics = trait_impl.trait_generics.iter().map(|generic| generic.to_string()).collect::<Vec<_>>();
+            format!("<{}>", trait_generics.join(", "))
+        };
+
+        let signature = format!(
+            "impl{} {}{} for {}{}",
+            generics(&trait_impl.impl_generics),
+            trait_impl.trait_name,
+            trait_generics,
+            trait_impl.object_type,
+            where_clause(&trait_impl.where_clause),
+        );
+        let functions = trait_impl.items.iter().filter_map(|item| match item {
+            // methods of a trait implementation are as visible as the trait
+            TraitImplItem::Function(method) => Some(Implementation::method(method, source, ItemVisibility::Public)),
+            _ => None,
+        }).collect();
+
+        Implementation {
+            signature,
+            trait_name: Some(trait_impl.trait_name.to_string()),
+            self_type: type_path(&trait_impl.object_type).unwrap_or_default(),
+            self_path: Vec::new(),
+            functions,
+            span: source.span(span),
+            source_link: String::new(),
+            self_link: String::new(),
+        }
+    }
 
-        res
+    fn method(method: &NoirFunction, source: &SourceFile, visibility: ItemVisibility) -> Function {
+        Function {
+            name: method.name().to_string(),
+            doc: doc(&source.tokens, &method.def.name),
+            signature: fn_signature(&method.def),
+            is_method: true,
+            visibility,
+            attributes: FunctionAttributes::new(&method.def),
+            span: source.item_span(method.def.name.span().merge(method.def.span), &method.def.name),
+            source_link: String::new(),
+            assets: String::new(),
+            anchor_index: 0,
+        }
     }
 }
 
-fn generate_structure_pages(structure: Structure) -> Result<(), Box<dyn std::error::Error>> {
+fn generate_structure_pages(structure: Structure, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let rendered_html = structure.render().unwrap();
 
-    let output_file_name = format!("generated_doc/{}.html", structure.name);
+    let output_file_name = output_dir.join(Type::Struct.page_name(&structure.name));
 
     let mut file = File::create(output_file_name)?;
     file.write_all(rendered_html.as_bytes())?;
@@ -160,12 +337,59 @@ pub(crate) struct Trait {
     required_methods: Vec<Function>,
     provided_methods: Vec<Function>,
     implementations: Vec<Implementation>,
+    visibility: ItemVisibility,
+    source_link: String,
+    assets: String,
 }
 
-fn generate_trait_pages(r#trait: Trait) -> Result<(), Box<dyn std::error::Error>> {
+fn generate_trait_pages(r#trait: Trait, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let rendered_html = r#trait.render().unwrap();
 
-    let output_file_name = format!("generated_doc/{}.html", r#trait.name);
+    let output_file_name = output_dir.join(Type::Trait.page_name(&r#trait.name));
+
+    let mut file = File::create(output_file_name)?;
+    file.write_all(rendered_html.as_bytes())?;
+
+    Ok(())
+}
+
+#[derive(Debug, Template)]
+#[template(path = "global_template.html")]
+pub(crate) struct Global {
+    name: String,
+    doc: String,
+    signature: String,
+    visibility: ItemVisibility,
+    source_link: String,
+    assets: String,
+}
+
+fn generate_global_pages(global: Global, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let rendered_html = global.render().unwrap();
+
+    let output_file_name = output_dir.join(Type::Global.page_name(&global.name));
+
+    let mut file = File::create(output_file_name)?;
+    file.write_all(rendered_html.as_bytes())?;
+
+    Ok(())
+}
+
+#[derive(Debug, Template)]
+#[template(path = "type_alias_template.html")]
+pub(crate) struct TypeAlias {
+    name: String,
+    doc: String,
+    signature: String,
+    visibility: ItemVisibility,
+    source_link: String,
+    assets: String,
+}
+
+fn generate_type_alias_pages(alias: TypeAlias, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let rendered_html = alias.render().unwrap();
+
+    let output_file_name = output_dir.join(Type::TypeAlias.page_name(&alias.name));
 
     let mut file = File::create(output_file_name)?;
     file.write_all(rendered_html.as_bytes())?;
@@ -178,50 +402,87 @@ fn generate_trait_pages(r#trait: Trait) -> Result<(), Box<dyn std::error::Error>
 pub(crate) struct AllOutput {
     pub(crate) all_output: Vec<Output>,
     pub(crate) filename: String,
+    pub(crate) span: SourceSpan,
+    pub(crate) source_link: String,
+    pub(crate) assets: String,
+    pub(crate) search_page: String,
+}
+
+impl AllOutput {
+    /// the items of the module page grouped by their kind, the same way as in the markdown output
+    fn sections(&self) -> Vec<ItemSection> {
+        module_sections(&self.all_output)
+    }
 }
 
+/// the page of a contract, its items are listed by their role rather than by their kind
 #[derive(Debug, Template)]
-#[template(path = "search_results_template.html")]
-pub(crate) struct SearchResults {
-    results: Vec<Output>
+#[template(path = "contract_template.html")]
+pub(crate) struct ContractPage<'a> {
+    pub(crate) contract: &'a AllOutput,
+    pub(crate) sections: Vec<ItemSection>,
 }
 
-fn generate_search_page(res: SearchResults, module_name: String) -> Result<(), Box<dyn std::error::Error>> {
-    let rendered_html = res.render().unwrap();
+#[derive(Debug, Template)]
+#[template(path = "workspace_template.html")]
+pub(crate) struct WorkspaceIndex {
+    pub(crate) name: String,
+    pub(crate) packages: Vec<Package>,
+    pub(crate) assets: String,
+}
 
-    let filename = format!("generated_doc/search_results_{}.html", module_name);
+pub(crate) fn generate_workspace_page(index: WorkspaceIndex, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let rendered_html = index.render().unwrap();
 
-    let mut file = File::create(filename)?;
+    let mut file = File::create(output_dir.join("index.html"))?;
     file.write_all(rendered_html.as_bytes())?;
 
     Ok(())
 }
 
-pub(crate) fn extract_filename(filename_with_path: &str) -> Option<&str> {
-    let path = Path::new(filename_with_path);
+pub(crate) fn extract_filename(path: &Path) -> Option<&str> {
     match path.file_stem() {
         Some(file_stem) => file_stem.to_str(),
         None => None,
     }
 }
 
-pub(crate) fn generate_module_page(module: AllOutput) -> Result<(), Box<dyn std::error::Error>> {
-    let rendered_html = module.render().unwrap();
+/// generates the pages of the module and its items in the module directory
+/// every submodule gets its own directory inside it
+pub(crate) fn generate_module_page(mut module: AllOutput, page: &ModulePage) -> Result<(), Box<dyn std::error::Error>> {
+    module.source_link = page.source_link(&module.span);
+    module.assets = page.assets_link();
+    module.search_page = page.search_link();
 
-    let fname = format!("generated_doc/{}.html", module.filename);
+    generate_index_page(&module.render().unwrap(), page)?;
+    generate_item_pages(&module, page)
+}
 
-    let mut file = File::create(fname)?;
-    file.write_all(rendered_html.as_bytes())?;
+/// generates the contract page with the items grouped by their role, and the pages of the items in the contract directory
+fn generate_contract_page(mut contract: AllOutput, page: &ModulePage) -> Result<(), Box<dyn std::error::Error>> {
+    contract.source_link = page.source_link(&contract.span);
+    contract.assets = page.assets_link();
+    contract.search_page = page.search_link();
+
+    let sections = contract_sections(&contract.all_output);
+    generate_index_page(&ContractPage { contract: &contract, sections }.render().unwrap(), page)?;
+    generate_item_pages(&contract, page)
+}
 
-    let fname = format!("input_files/{}.nr", module.filename);
+fn generate_index_page(rendered_html: &str, page: &ModulePage) -> Result<(), Box<dyn std::error::Error>> {
+    let module_dir = page.dir();
+    fs::create_dir_all(&module_dir)?;
 
-    if fs::metadata(&fname).is_ok() {
-        generate_code_page(&fname)?;
-    }
+    let mut file = File::create(module_dir.join("index.html"))?;
+    file.write_all(rendered_html.as_bytes())?;
 
-    let res = SearchResults{ results: module.all_output.clone() };
+    Ok(())
+}
+
+fn generate_item_pages(module: &AllOutput, page: &ModulePage) -> Result<(), Box<dyn std::error::Error>> {
+    let module_dir = page.dir();
 
-    generate_search_page(res, module.filename)?;
+    generate_code_page(&module.span, page)?;
 
     for i in module.all_output.iter() {
         match i.r#type {
@@ -232,19 +493,18 @@ pub(crate) fn generate_module_page(module: AllOutput) -> Result<(), Box<dyn std:
                         doc: i.doc.clone(), 
                         signature: i.information.get_signature().unwrap(),
                         is_method: false, 
-                    }
+                        visibility: i.visibility,
+                        attributes: i.information.get_attributes().unwrap(),
+                        span: i.span.clone(),
+                        source_link: page.source_link(&i.span),
+                        assets: module.assets.clone(),
+                        anchor_index: 0,
+                    },
+                    &module_dir
                 )?;
             } 
             Type::Struct => {
-                generate_structure_pages(
-                    Structure { 
-                        name: i.name.clone(), 
-                        doc: i.doc.clone(), 
-                        additional_doc: i.information.get_additional_doc().unwrap(),
-                        signature: i.information.get_signature().unwrap(), 
-                        implementations: i.information.get_implementations().unwrap()
-                    } 
-                )?;
+                generate_structure_pages(Structure::new(i, page, &module.assets), &module_dir)?;
             } 
             Type::Trait => {
                 generate_trait_pages(
@@ -253,18 +513,66 @@ pub(crate) fn generate_module_page(module: AllOutput) -> Result<(), Box<dyn std:
                         doc: i.doc.clone(), 
                         signature: i.information.get_signature().unwrap(), 
                         additional_doc: i.information.get_additional_doc().unwrap(),
-                        required_methods: i.information.get_required_methods().unwrap(), 
-                        provided_methods: i.information.get_provided_methods().unwrap(), 
-                        implementations: i.information.get_implementations().unwrap()
-                    }
+                        required_methods: page.methods(i.information.get_required_methods().unwrap()), 
+                        provided_methods: page.methods(i.information.get_provided_methods().unwrap()), 
+                        implementations: page.implementations(i.information.get_implementations().unwrap()),
+                        visibility: i.visibility,
+                        source_link: page.source_link(&i.span),
+                        assets: module.assets.clone(),
+                    },
+                    &module_dir
+                )?;
+            }
+            Type::Global => {
+                generate_global_pages(
+                    Global {
+                        name: i.name.clone(),
+                        doc: i.doc.clone(),
+                        signature: i.information.get_signature().unwrap(),
+                        visibility: i.visibility,
+                        source_link: page.source_link(&i.span),
+                        assets: module.assets.clone(),
+                    },
+                    &module_dir
+                )?;
+            }
+            Type::TypeAlias => {
+                generate_type_alias_pages(
+                    TypeAlias {
+                        name: i.name.clone(),
+                        doc: i.doc.clone(),
+                        signature: i.information.get_signature().unwrap(),
+                        visibility: i.visibility,
+                        source_link: page.source_link(&i.span),
+                        assets: module.assets.clone(),
+                    },
+                    &module_dir
                 )?;
             }
             Type::Module => {
                 generate_module_page(
                     AllOutput { 
                         all_output: i.information.get_content().unwrap(), 
-                        filename: i.name.clone() 
-                    } 
+                        filename: i.name.clone(),
+                        span: i.span.clone(),
+                        source_link: String::new(),
+                        assets: String::new(),
+                        search_page: String::new(),
+                    },
+                    &page.child(&i.name)
+                )?;
+            }
+            Type::Contract => {
+                generate_contract_page(
+                    AllOutput {
+                        all_output: i.information.get_content().unwrap(),
+                        filename: i.name.clone(),
+                        span: i.span.clone(),
+                        source_link: String::new(),
+                        assets: String::new(),
+                        search_page: String::new(),
+                    },
+                    &page.child(&i.name)
                 )?;
             }
             _ => {}
@@ -0,0 +1,216 @@
+use std::{fs::File, io::Write, path::Path};
+
+use askama::Template;
+use serde::Serialize;
+
+use crate::{DocLinks, Function, Info, Output, Type, ASSETS_DIR, SEARCH_PAGE, summary_html};
+
+/// file with the search index in the crate documentation directory
+pub(crate) const SEARCH_INDEX: &str = "search-index.js";
+
+/// an item of the crate-wide search index
+#[derive(Debug, Serialize)]
+pub(crate) struct SearchEntry {
+    pub(crate) name: String,
+    /// `fn`, `mod`, `contract`, `struct`, `trait`, `global`, `type`, `method` or `field`, also used as the search filter
+    pub(crate) kind: &'static str,
+    /// path of the module or the item the entry belongs to, e.g. `my_crate::shapes::Point`
+    pub(crate) path: String,
+    /// link to the documentation page relative to the crate directory
+    pub(crate) link: String,
+    pub(crate) doc: String,
+    pub(crate) summary: String,
+    /// parameter types of functions and methods
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) inputs: Vec<String>,
+    /// return type of functions and methods, empty for other items and functions returning nothing
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub(crate) output: String,
+    /// functions, methods and structs marked with `#[deprecated]`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) deprecated: bool,
+}
+
+impl SearchEntry {
+    fn new(name: &str, kind: &'static str, path: &str, link: &str, doc: &str) -> Self {
+        SearchEntry {
+            name: name.to_string(),
+            kind,
+            path: path.to_string(),
+            link: link.to_string(),
+            doc: doc.trim().to_string(),
+            summary: String::new(),
+            inputs: Vec::new(),
+            output: String::new(),
+            deprecated: false,
+        }
+    }
+
+    fn function(name: &str, kind: &'static str, path: &str, link: &str, doc: &str, signature: &str) -> Self {
+        let (inputs, output) = signature_types(signature);
+
+        SearchEntry { inputs, output, ..SearchEntry::new(name, kind, path, link, doc) }
+    }
+}
+
+/// nesting depth change of a bracket character, `>` of an `->` arrow is not a bracket
+fn bracket_depth(text: &str, i: usize, c: char) -> i32 {
+    match c {
+        '(' | '[' | '{' | '<' => 1,
+        '>' if text[..i].ends_with('-') => 0,
+        ')' | ']' | '}' | '>' => -1,
+        _ => 0,
+    }
+}
+
+/// splits the text at every separator that is not nested in brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        depth += bracket_depth(text, i, c);
+        if depth == 0 && c == separator {
+            res.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    res.push(&text[start..]);
+
+    res
+}
+
+/// the type of a `pattern: type` parameter, the first single `:` outside of brackets separates them
+fn parameter_type(parameter: &str) -> &str {
+    let mut depth = 0;
+
+    for (i, c) in parameter.char_indices() {
+        depth += bracket_depth(parameter, i, c);
+        if depth == 0 && c == ':' && !parameter[..i].ends_with(':') && !parameter[i + 1..].starts_with(':') {
+            return parameter[i + 1..].trim().trim_start_matches("pub ");
+        }
+    }
+
+    parameter.trim()
+}
+
+/// extracts the parameter types and the return type of a function signature
+/// e.g. `fn foo<T>(x: Field, y: pub [u8; 32]) -> distinct pub bool where T: Eq` gives `Field`, `[u8; 32]` and `bool`
+pub(crate) fn signature_types(signature: &str) -> (Vec<String>, String) {
+    let Some(start) = signature.find("fn ").and_then(|fn_start| signature[fn_start..].find('(').map(|i| fn_start + i)) else {
+        return (Vec::new(), String::new());
+    };
+
+    let mut depth = 0;
+    let mut end = signature.len();
+    for (i, c) in signature[start..].char_indices() {
+        depth += bracket_depth(&signature[start..], i, c);
+        if depth == 0 {
+            end = start + i;
+            break;
+        }
+    }
+
+    let inputs = split_top_level(&signature[start + 1..end], ',').into_iter()
+        .filter(|parameter| !parameter.trim().is_empty())
+        .map(|parameter| parameter_type(parameter).to_string())
+        .collect();
+
+    let rest = signature.get(end + 1..).unwrap_or_default().trim();
+    let output = match rest.strip_prefix("->") {
+        Some(output) => {
+            let output = output.split(" where ").next().unwrap_or_default().trim();
+            output.trim_start_matches("distinct ").trim_start_matches("pub ").trim().to_string()
+        }
+        None => String::new(),
+    };
+
+    (inputs, output)
+}
+
+/// collects the search entries of all modules, items, methods and fields of the crate
+/// the intra-doc links of the summaries are made relative to the search page in the crate directory
+pub(crate) fn search_index(name: &str, items: &[Output], links: &DocLinks) -> Vec<SearchEntry> {
+    let mut res = Vec::new();
+    collect_entries(&mut res, &[name.to_string()], items, links);
+
+    res
+}
+
+fn collect_entries(res: &mut Vec<SearchEntry>, path: &[String], items: &[Output], links: &DocLinks) {
+    let module = path.join("::");
+    let dir = path[1..].iter().map(|name| format!("{}/", name)).collect::<String>();
+
+    for item in items.iter() {
+        let kind = match item.r#type {
+            Type::Function => "fn",
+            Type::Module => "mod",
+            Type::Contract => "contract",
+            Type::Struct => "struct",
+            Type::Trait => "trait",
+            Type::Global => "global",
+            Type::TypeAlias => "type",
+            Type::OuterComment => continue,
+        };
+        let link = format!("{}{}", dir, item.link());
+        let item_path = format!("{}::{}", module, item.name);
+
+        let start = res.len();
+
+        match &item.information {
+            Info::Function { signature, .. } => res.push(SearchEntry::function(&item.name, kind, &module, &link, &item.doc, signature)),
+            _ => res.push(SearchEntry::new(&item.name, kind, &module, &link, &item.doc)),
+        }
+        res[start].deprecated = item.is_deprecated();
+
+        for field in item.information.get_fields().unwrap_or_default() {
+            res.push(SearchEntry::new(&field.name, "field", &item_path, &format!("{}#{}", link, field.anchor()), &field.doc));
+        }
+        for implementation in item.information.get_implementations().filter(|_| item.r#type == Type::Struct).unwrap_or_default() {
+            method_entries(res, &implementation.functions, &item_path, &link);
+        }
+        method_entries(res, &item.information.get_required_methods().unwrap_or_default(), &item_path, &link);
+        method_entries(res, &item.information.get_provided_methods().unwrap_or_default(), &item_path, &link);
+
+        for entry in res[start..].iter_mut() {
+            entry.summary = summary_html(&links.rewrite(&entry.doc, &path[1..], &[]).0);
+        }
+
+        if let Some(content) = item.information.get_content() {
+            let mut child = path.to_vec();
+            child.push(item.name.clone());
+
+            collect_entries(res, &child, &content, links);
+        }
+    }
+}
+
+fn method_entries(res: &mut Vec<SearchEntry>, methods: &[Function], path: &str, link: &str) {
+    for method in methods.iter() {
+        let entry = SearchEntry::function(&method.name, "method", path, &format!("{}#{}", link, method.anchor()), &method.doc, &method.signature);
+        res.push(SearchEntry { deprecated: method.attributes.deprecated.is_some(), ..entry });
+    }
+}
+
+#[derive(Debug, Template)]
+#[template(path = "search_results_template.html")]
+pub(crate) struct SearchResults {
+    assets: String,
+    search_index: String,
+}
+
+/// writes the search page and the search index to the crate documentation directory
+/// the index is a script rather than a json file, browsers do not allow fetching files from `file://` pages
+pub(crate) fn generate_search_page(index: &[SearchEntry], crate_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = File::create(crate_dir.join(SEARCH_INDEX))?;
+    file.write_all(format!("window.searchIndex = {};\n", serde_json::to_string(index)?).as_bytes())?;
+
+    let res = SearchResults { assets: format!("../{}", ASSETS_DIR), search_index: SEARCH_INDEX.to_string() };
+    let rendered_html = res.render().unwrap();
+
+    let mut file = File::create(crate_dir.join(SEARCH_PAGE))?;
+    file.write_all(rendered_html.as_bytes())?;
+
+    Ok(())
+}
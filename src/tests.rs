@@ -1,29 +1,29 @@
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, path::{Path, PathBuf}};
 
-    use crate::{generate_doc, Info, get_map, Map};
+    use crate::{AllOutput, attribute_name, contract_sections, contract_visibility, link_implementations, without_tests, FunctionAttributes, generate_doc, generate_project_doc, resolve_workspace, get_doc, Info, get_map, Map, ModuleLocation, Output, OutputFormat, PackageType, Config, JsonCrate, SourceSpan, DocSpan, Type, summary, render_html, summary_html, generate_assets, search_index, signature_types, DocLinks, LinkStyle, UnresolvedLink, Import, check_links, Field, Function, Implementation, ASSETS_DIR, doc_tests, hide_doc_test_lines, DocTestResult, TestCrate, PackageCoverage, Count, ItemVisibility, lint_crate, lint_tokens, sarif, Lint, LintLevels, Level, public_api, ModulePage, Structure, clean, OUTPUT_MARKER};
 
     #[test]
     fn one_file() {
-        assert!(generate_doc("input_files/another_module.nr").is_ok());
+        assert!(generate_doc(Path::new("input_files/another_module.nr"), &Config::default()).is_ok());
     }
 
     #[test]
     fn many_files() {
-        assert!(generate_doc("input_files/prog.nr").is_ok());
+        assert!(generate_doc(Path::new("input_files/prog.nr"), &Config::default()).is_ok());
     }
 
     #[test]
     fn function_output() {
         let mut map = HashMap::new();
-        map.insert(Info::Function { signature: "fn main ( x : Field , y : pub Field ) ".to_string() }, "doc comment".to_string());
+        map.insert(Info::Function { signature: "fn main(x: Field, y: pub Field)".to_string(), attributes: FunctionAttributes::default() }, "doc comment".to_string());
 
         let result = Map { 
             map
         };
 
-        assert_eq!(get_map("input_files/function_example.nr"), result);
+        assert_eq!(get_map(Path::new("input_files/function_example.nr")).unwrap(), result);
     }
 
     #[test]
@@ -31,9 +31,15 @@ mod tests {
         let mut map = HashMap::new();
         map.insert(
             Info::Struct { 
-                signature: "struct MyStruct {\n/* private fields */\n}".to_string(), 
+                signature: "struct MyStruct {\n    mana: i32,\n    name: String,\n}".to_string(), 
                 additional_doc: "".to_string(), 
-                implementations: vec![] 
+                fields: vec![
+                    Field { name: "mana".to_string(), r#type: "i32".to_string(), doc: String::new(), visibility: ItemVisibility::Private, span: SourceSpan::new(PathBuf::from("input_files/struct_example.nr"), 3, 3) },
+                    Field { name: "name".to_string(), r#type: "String".to_string(), doc: String::new(), visibility: ItemVisibility::Private, span: SourceSpan::new(PathBuf::from("input_files/struct_example.nr"), 4, 4) },
+                ],
+                implementations: vec![],
+                attributes: vec![],
+                deprecated: None,
             }, 
             "struct".to_string());
 
@@ -41,6 +47,874 @@ mod tests {
             map
         };
 
-        assert_eq!(get_map("input_files/struct_example.nr"), result);
+        assert_eq!(get_map(Path::new("input_files/struct_example.nr")).unwrap(), result);
+    }
+
+    #[test]
+    fn globals_and_type_aliases() {
+        let entry = Path::new("input_files/globals.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+
+        assert_eq!(items.iter().map(|item| (item.r#type, item.name.as_str(), item.doc.as_str(), item.visibility, item.span.start_line)).collect::<Vec<_>>(), vec![
+            (Type::Global, "MAX", " Largest coordinate.", ItemVisibility::Public, 2),
+            (Type::Global, "MIN", "", ItemVisibility::Public, 4),
+            (Type::TypeAlias, "Coordinates", " A pair of coordinates.", ItemVisibility::Public, 7),
+            (Type::TypeAlias, "Pair", "", ItemVisibility::Public, 9),
+        ]);
+        assert_eq!(items[0].information, Info::Global { signature: "global MAX: Field = 100;".to_string(), r#type: "Field".to_string(), value: "100".to_string() });
+        assert_eq!(items[1].information, Info::Global { signature: "global MIN = 0;".to_string(), r#type: String::new(), value: "0".to_string() });
+        assert_eq!(items[2].information, Info::TypeAlias { signature: "type Coordinates = [Field; 2];".to_string(), aliased_type: "[Field; 2]".to_string() });
+        assert_eq!(items[3].information, Info::TypeAlias { signature: "type Pair<T> = (T, T);".to_string(), aliased_type: "(T, T)".to_string() });
+    }
+
+    #[test]
+    fn trait_method_spans() {
+        let entry = Path::new("input_files/trait_methods.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+
+        // the spans cover the whole declaration or body, like the spans of impl methods
+        let lines = |methods: Vec<Function>| methods.iter().map(|method| (method.name.clone(), method.span.start_line, method.span.end_line)).collect::<Vec<_>>();
+        assert_eq!(lines(items[0].information.get_required_methods().unwrap()), vec![("area".to_string(), 4, 5)]);
+        assert_eq!(lines(items[0].information.get_provided_methods().unwrap()), vec![("is_empty".to_string(), 8, 11)]);
+    }
+
+    #[test]
+    fn workspace_discovery() {
+        let workspace = resolve_workspace(Path::new("input_files/workspace")).unwrap();
+
+        assert!(workspace.is_workspace);
+        assert_eq!(workspace.members.len(), 2);
+        assert_eq!(workspace.members[0].name, "hashing");
+        assert_eq!(workspace.members[0].r#type, PackageType::Library);
+        assert!(workspace.members[0].entry_path.ends_with("src/lib.nr"));
+        assert_eq!(workspace.members[1].name, "circuit");
+        assert_eq!(workspace.members[1].r#type, PackageType::Binary);
+        assert!(workspace.members[1].entry_path.ends_with("src/main.nr"));
+    }
+
+    #[test]
+    fn package_discovery() {
+        let workspace = resolve_workspace(Path::new("input_files/workspace/crates/circuit/src")).unwrap();
+
+        assert!(!workspace.is_workspace);
+        assert_eq!(workspace.members.len(), 1);
+        assert_eq!(workspace.members[0].name, "circuit");
+    }
+
+    #[test]
+    fn workspace_output() {
+        let output_dir = std::env::temp_dir().join("doc_generator_workspace_output");
+
+        assert!(generate_project_doc(Path::new("input_files/workspace"), &Config { output_dir: output_dir.clone(), ..Config::default() }).is_ok());
+        assert!(output_dir.join("index.html").is_file());
+        assert!(output_dir.join("hashing").join("index.html").is_file());
+        assert!(output_dir.join("hashing").join("fn.hash_pair.html").is_file());
+        assert!(output_dir.join("circuit").join("index.html").is_file());
+    }
+
+    fn module_functions(output: &[Output], module: &str) -> Vec<String> {
+        let content = output.iter()
+            .find(|item| item.r#type == Type::Module && item.name == module)
+            .and_then(|item| item.information.get_content())
+            .unwrap();
+
+        content.iter().filter(|item| item.r#type == Type::Function).map(|item| item.name.clone()).collect()
+    }
+
+    /// file the spans of the hand-built items point into, every item is given its own line
+    const FIXTURE_FILE: &str = "src/main.nr";
+
+    fn span(line: usize) -> SourceSpan {
+        SourceSpan::new(PathBuf::from(FIXTURE_FILE), line, line)
+    }
+
+    /// a public undocumented item, the other fields are set with the struct update syntax
+    fn item(r#type: Type, name: &str, information: Info, line: usize) -> Output {
+        Output { r#type, name: name.to_string(), doc: String::new(), information, visibility: ItemVisibility::Public, span: span(line) }
+    }
+
+    fn function(name: &str, attributes: FunctionAttributes, line: usize) -> Output {
+        item(Type::Function, name, Info::Function { signature: format!("fn {}()", name), attributes }, line)
+    }
+
+    fn module(name: &str, content: Vec<Output>, line: usize) -> Output {
+        item(Type::Module, name, Info::Module { content, imports: vec![], impls: vec![] }, line)
+    }
+
+    fn struct_info(signature: &str, fields: Vec<Field>, implementations: Vec<Implementation>, attributes: &[&str]) -> Info {
+        Info::Struct {
+            signature: signature.to_string(),
+            additional_doc: String::new(),
+            fields,
+            implementations,
+            attributes: attributes.iter().map(|attribute| attribute.to_string()).collect(),
+            deprecated: None,
+        }
+    }
+
+    fn trait_info(required_methods: Vec<Function>) -> Info {
+        Info::Trait { signature: String::new(), additional_doc: String::new(), required_methods, provided_methods: vec![], implementations: vec![] }
+    }
+
+    fn field(name: &str, line: usize) -> Field {
+        Field { name: name.to_string(), r#type: "Field".to_string(), doc: String::new(), visibility: ItemVisibility::Public, span: span(line) }
+    }
+
+    fn method(name: &str, line: usize) -> Function {
+        Function { name: name.to_string(), doc: String::new(), signature: format!("fn {}()", name), is_method: true, visibility: ItemVisibility::Public, attributes: FunctionAttributes::default(), span: span(line), source_link: String::new(), assets: String::new(), anchor_index: 0 }
+    }
+
+    fn implementation(signature: &str, trait_name: Option<&str>, self_type: &str, functions: Vec<Function>, line: usize) -> Implementation {
+        Implementation {
+            signature: signature.to_string(),
+            trait_name: trait_name.map(str::to_string),
+            self_type: self_type.to_string(),
+            self_path: vec![],
+            functions,
+            span: span(line),
+            source_link: String::new(),
+            self_link: String::new(),
+        }
+    }
+
+    #[test]
+    fn nested_modules() {
+        let entry = Path::new("input_files/nested/main.nr");
+        let output = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+
+        let foo = output.iter().find(|item| item.name == "foo").and_then(|item| item.information.get_content()).unwrap();
+        let bar = output.iter().find(|item| item.name == "bar").and_then(|item| item.information.get_content()).unwrap();
+
+        assert_eq!(module_functions(&foo, "baz"), vec!["baz_fn".to_string()]);
+        assert_eq!(module_functions(&bar, "qux"), vec!["qux_fn".to_string()]);
+    }
+
+    #[test]
+    fn missing_module() {
+        let err = get_map(Path::new("input_files/missing_module.nr")).unwrap_err().to_string();
+
+        assert!(err.contains("`does_not_exist`"));
+        assert!(err.contains("missing_module.nr:3"));
+    }
+
+    #[test]
+    fn output_layout() {
+        let output_dir = std::env::temp_dir().join("doc_generator_output_layout");
+        let config = Config { output_dir: output_dir.clone(), document_private_items: true, ..Config::default() };
+
+        assert!(generate_doc(Path::new("input_files/layout.nr"), &config).is_ok());
+
+        let crate_dir = output_dir.join("layout");
+        assert!(crate_dir.join("index.html").is_file());
+        assert!(crate_dir.join("search.html").is_file());
+        assert!(crate_dir.join("struct.points.html").is_file());
+        assert!(crate_dir.join("points").join("index.html").is_file());
+        assert!(crate_dir.join("points").join("fn.new.html").is_file());
+        assert!(crate_dir.join("shapes").join("fn.new.html").is_file());
+        assert!(crate_dir.join("src").join("layout.nr.html").is_file());
+        assert!(output_dir.join(OUTPUT_MARKER).is_file());
+    }
+
+    #[test]
+    fn clean_output() {
+        let output_dir = std::env::temp_dir().join("doc_generator_clean");
+        std::fs::create_dir_all(&output_dir).unwrap();
+        std::fs::write(output_dir.join("notes.txt"), "").unwrap();
+        let _ = std::fs::remove_file(output_dir.join(OUTPUT_MARKER));
+
+        // a directory `build` did not write is left untouched
+        assert!(clean(&output_dir).is_err());
+        assert!(output_dir.join("notes.txt").is_file());
+
+        std::fs::write(output_dir.join(OUTPUT_MARKER), "").unwrap();
+        assert!(clean(&output_dir).is_ok());
+        assert!(!output_dir.exists());
+        assert!(clean(&output_dir).is_ok());
+    }
+
+    #[test]
+    fn source_pages() {
+        let output_dir = std::env::temp_dir().join("doc_generator_source_pages");
+        let config = Config { output_dir: output_dir.clone(), ..Config::default() };
+
+        assert!(generate_doc(Path::new("input_files/nested/main.nr"), &config).is_ok());
+
+        let src_dir = output_dir.join("main").join("src");
+        assert!(src_dir.join("main.nr.html").is_file());
+        assert!(src_dir.join("foo.nr.html").is_file());
+        assert!(src_dir.join("foo").join("baz.nr.html").is_file());
+        assert!(src_dir.join("bar").join("mod.nr.html").is_file());
+    }
+
+    #[test]
+    fn source_link() {
+        let span = SourceSpan::new(PathBuf::from("input_files/nested/foo/baz.nr"), 2, 4);
+
+        assert_eq!(span.link(Path::new("input_files/nested")), "src/foo/baz.nr.html#L2-L4");
+    }
+
+    fn json_snapshot(name: &str) -> (serde_json::Value, serde_json::Value) {
+        let entry = PathBuf::from(format!("input_files/{}.nr", name));
+        let location = ModuleLocation::crate_root(&entry);
+        let source = get_doc(&entry).unwrap();
+        let output = Output::to_output(&source, &location).unwrap();
+
+        let json = JsonCrate::new(name.to_string(), &output, &source.file_span(), &location.child_dir);
+        let snapshot = std::fs::read_to_string(format!("input_files/snapshots/{}.json", name)).unwrap();
+
+        (serde_json::to_value(json).unwrap(), serde_json::from_str(&snapshot).unwrap())
+    }
+
+    #[test]
+    fn function_json() {
+        let (json, snapshot) = json_snapshot("function_example");
+
+        assert_eq!(json, snapshot);
+    }
+
+    #[test]
+    fn structure_json() {
+        let (json, snapshot) = json_snapshot("struct_example");
+
+        assert_eq!(json, snapshot);
+    }
+
+    #[test]
+    fn markdown_pages() {
+        let output_dir = std::env::temp_dir().join("doc_generator_markdown_pages");
+        let config = Config { output_dir: output_dir.clone(), format: OutputFormat::Markdown, document_private_items: true, ..Config::default() };
+
+        assert!(generate_doc(Path::new("input_files/layout.nr"), &config).is_ok());
+
+        let crate_dir = output_dir.join("layout");
+        assert!(crate_dir.join("index.md").is_file());
+        assert!(crate_dir.join("struct.points.md").is_file());
+        assert!(crate_dir.join("points").join("fn.new.md").is_file());
+
+        let index = std::fs::read_to_string(crate_dir.join("index.md")).unwrap();
+        assert!(index.contains("| [points](points/index.md) |"));
+        assert!(index.contains("| [points](struct.points.md) |"));
+    }
+
+    #[test]
+    fn markdown_single_file() {
+        let output_dir = std::env::temp_dir().join("doc_generator_markdown_single_file");
+        let config = Config { output_dir: output_dir.clone(), format: OutputFormat::Markdown, single_file: true, document_private_items: true, ..Config::default() };
+
+        assert!(generate_doc(Path::new("input_files/layout.nr"), &config).is_ok());
+
+        let markdown = std::fs::read_to_string(output_dir.join("layout.md")).unwrap();
+        assert!(markdown.contains("<a id=\"layout/points/fn.new\"></a>"));
+        assert!(markdown.contains("| [new](#layout/points/fn.new) |"));
+    }
+
+    #[test]
+    fn markdown_summary() {
+        assert_eq!(summary("first line\nsecond | line\n\nmore details"), "first line second \\| line");
+    }
+
+    #[test]
+    fn markdown_rendering() {
+        let doc = " Adds two numbers.\n\n | a | b |\n | - | - |\n | 1 | 2 |\n\n ```noir\n let x = add(1, 2);\n ```\n\n See the note[^1].\n\n [^1]: a footnote";
+        let html = render_html(doc);
+
+        assert!(html.starts_with("<p>Adds two numbers.</p>"));
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<pre><code class=\"language-noir\">let x = add(1, 2);\n</code></pre>"));
+        assert!(html.contains("class=\"footnote-definition\""));
+
+        assert_eq!(summary_html(doc), "Adds two numbers.");
+        assert_eq!(summary_html(" Uses `Field`\n values."), "Uses <code>Field</code> values.");
+    }
+
+    #[test]
+    fn assets_bundle() {
+        let output_dir = std::env::temp_dir().join("doc_generator_assets_bundle");
+
+        assert!(generate_assets(&output_dir).is_ok());
+        for name in ["style.css", "search.js", "search_results.js", "source.js", "search.svg", "favicon.svg"] {
+            assert!(output_dir.join(ASSETS_DIR).join(name).is_file());
+        }
+
+        for template in std::fs::read_dir("templates").unwrap() {
+            let text = std::fs::read_to_string(template.unwrap().path()).unwrap();
+            assert!(!text.contains("http://") && !text.contains("https://"));
+            assert!(!text.contains("<style>"));
+        }
+    }
+
+    #[test]
+    fn search_entries() {
+        let new = Function { doc: "Creates a point.".to_string(), signature: "fn new() -> Point".to_string(), ..method("new", 7) };
+        let point = Output {
+            doc: "A point.\n\nMore details.".to_string(),
+            ..item(Type::Struct, "Point", struct_info("struct Point {\n    pub x: Field,\n}", vec![field("x", 3)], vec![implementation("impl Point", None, "Point", vec![new], 6)], &[]), 2)
+        };
+        let max = Output {
+            doc: "Largest coordinate.".to_string(),
+            ..item(Type::Global, "MAX", Info::Global { signature: "global MAX: Field = 100;".to_string(), r#type: "Field".to_string(), value: "100".to_string() }, 10)
+        };
+        let coordinates = item(Type::TypeAlias, "Coordinates", Info::TypeAlias { signature: "type Coordinates = [Field; 2];".to_string(), aliased_type: "[Field; 2]".to_string() }, 11);
+        let shapes = module("shapes", vec![point, max, coordinates], 1);
+
+        let items = [shapes];
+        let links = DocLinks::new(&items, &[], LinkStyle::Pages("html"));
+
+        let index = search_index("layout", &items, &links);
+        let entries = index.iter().map(|entry| (entry.kind, entry.path.as_str(), entry.name.as_str(), entry.link.as_str())).collect::<Vec<_>>();
+
+        assert_eq!(entries, vec![
+            ("mod", "layout", "shapes", "shapes/index.html"),
+            ("struct", "layout::shapes", "Point", "shapes/struct.Point.html"),
+            ("field", "layout::shapes::Point", "x", "shapes/struct.Point.html#structfield.x"),
+            ("method", "layout::shapes::Point", "new", "shapes/struct.Point.html#method.new"),
+            ("global", "layout::shapes", "MAX", "shapes/global.MAX.html"),
+            ("type", "layout::shapes", "Coordinates", "shapes/type.Coordinates.html"),
+        ]);
+        assert_eq!(index[1].summary, "A point.");
+    }
+
+    #[test]
+    fn signature_search_types() {
+        assert_eq!(
+            signature_types("pub fn foo<T>(x: Field, (a, b): (u8, u8), y: pub [u8; 32], f: fn(Field) -> bool) -> distinct pub Map<T, std::hash::Hash> where T: Eq"),
+            (vec!["Field".to_string(), "(u8, u8)".to_string(), "[u8; 32]".to_string(), "fn(Field) -> bool".to_string()], "Map<T, std::hash::Hash>".to_string())
+        );
+        assert_eq!(signature_types("fn main()"), (vec![], String::new()));
+        assert_eq!(signature_types("pub(crate) fn eq(self, other: Self) -> bool"), (vec!["self".to_string(), "Self".to_string()], "bool".to_string()));
+    }
+
+    fn doc_links_tree() -> Vec<Output> {
+        let new = Function { signature: "fn new() -> Point".to_string(), ..method("new", 7) };
+        let point = struct_info("", vec![field("x", 4)], vec![implementation("impl Point", None, "Point", vec![new], 6)], &[]);
+
+        let deep = module("deep", vec![function("area", FunctionAttributes::default(), 10)], 9);
+        let shapes = module("shapes", vec![item(Type::Struct, "Point", point, 3), deep], 2);
+
+        let max = item(Type::Global, "MAX", Info::Global { signature: String::new(), r#type: String::new(), value: "10".to_string() }, 13);
+        let hash = item(Type::TypeAlias, "Hash", Info::TypeAlias { signature: String::new(), aliased_type: "[u8; 32]".to_string() }, 14);
+
+        let helper = struct_info("", vec![], vec![implementation("impl Helper", None, "Helper", vec![method("new", 16)], 15)], &[]);
+
+        vec![item(Type::Struct, "Helper", helper, 1), function("helper", FunctionAttributes::default(), 12), max, hash, shapes]
+    }
+
+    #[test]
+    fn doc_links() {
+        let items = doc_links_tree();
+        let imports = vec![Import { alias: "P".to_string(), path: vec!["crate".to_string(), "shapes".to_string(), "Point".to_string()] }];
+        let links = DocLinks::new(&items, &imports, LinkStyle::Pages("html"));
+
+        let root = Vec::new();
+        let deep = vec!["shapes".to_string(), "deep".to_string()];
+        let rewrite = |doc: &str, module: &[String]| links.rewrite(doc, module, module).0;
+
+        assert_eq!(rewrite("See [Helper] and [`Helper::new`].", &root), "See [Helper](struct.Helper.html) and [`Helper::new`](struct.Helper.html#method.new).");
+        assert_eq!(rewrite("[helper function](helper), [P] and [the module](shapes)", &root), "[helper function](fn.helper.html), [P](shapes/struct.Point.html) and [the module](shapes/index.html)");
+        assert_eq!(rewrite("[Point](struct.Point.html)", &["shapes".to_string()]), "[Point](struct.Point.html)");
+        assert_eq!(rewrite("[super::Point], [crate::Helper], [area()]", &deep), "[super::Point](../struct.Point.html), [crate::Helper](../../struct.Helper.html), [area()](fn.area.html)");
+        assert_eq!(rewrite("[`Point::x`] and [Point::new]", &["shapes".to_string()]), "[`Point::x`](struct.Point.html#structfield.x) and [Point::new](struct.Point.html#method.new)");
+        assert_eq!(rewrite("[MAX] and [the hash](type.Hash.html)", &root), "[MAX](global.MAX.html) and [the hash](type.Hash.html)");
+        assert_eq!(rewrite("[crate::MAX], [crate::Hash]", &deep), "[crate::MAX](../../global.MAX.html), [crate::Hash](../../type.Hash.html)");
+        assert_eq!(rewrite("the [helper][h] docs\n\n[h]: Helper", &root), "the [helper][h] docs\n\n[h]: struct.Helper.html");
+        assert_eq!(rewrite("range `[low, high]`, [low, high] and [rhai](https://docs.rs/crate/rhai/)", &root), "range `[low, high]`, [low, high] and [rhai](https://docs.rs/crate/rhai/)");
+
+        let (_, unresolved) = links.rewrite(" First line.\n\n See [Point] and [HelperDef](trait.HelperDef.html).\n [`Helper::typo`]", &root, &root);
+        assert_eq!(unresolved, vec![
+            UnresolvedLink { line: 2, link: "Point".to_string() },
+            UnresolvedLink { line: 2, link: "trait.HelperDef.html".to_string() },
+            UnresolvedLink { line: 3, link: "`Helper::typo`".to_string() },
+        ]);
+
+        let anchors = DocLinks::new(&items, &imports, LinkStyle::Anchors("layout".to_string()));
+        assert_eq!(anchors.rewrite("[P] in [shapes]", &root, &root).0, "[P](#layout/shapes/struct.Point) in [shapes](#layout/shapes)");
+        assert_eq!(anchors.rewrite("[P::x]", &root, &root).0, "[P::x](#layout/shapes/struct.Point/structfield.x)");
+        assert_eq!(anchors.rewrite("[P::new()]", &root, &root).0, "[P::new()](#layout/shapes/struct.Point/method.new)");
+    }
+
+    #[test]
+    fn crate_implementations() {
+        let shapes = Output {
+            information: Info::Module { content: vec![item(Type::Struct, "Point", struct_info("", vec![], vec![], &[]), 3)], imports: vec![], impls: vec![
+                implementation("impl Point", None, "Point", vec![], 5),
+            ] },
+            ..module("shapes", vec![], 2)
+        };
+        let other = Output {
+            information: Info::Module {
+                content: vec![],
+                imports: vec![Import { alias: "P".to_string(), path: vec!["crate".to_string(), "shapes".to_string(), "Point".to_string()] }],
+                impls: vec![
+                    implementation("impl crate::Shape for P", Some("crate::Shape"), "P", vec![], 10),
+                    implementation("impl Eq for P", Some("Eq"), "P", vec![], 12),
+                ],
+            },
+            ..module("other", vec![], 8)
+        };
+        let mut items = vec![item(Type::Trait, "Shape", trait_info(vec![]), 1), shapes, other];
+
+        link_implementations(&mut items, &[], vec![implementation("impl Shape for Field", Some("Shape"), "Field", vec![], 20)]);
+
+        let signatures = |implementations: Vec<Implementation>| implementations.iter().map(|implementation| (implementation.signature.clone(), implementation.self_path.join("::"))).collect::<Vec<_>>();
+        let pair = |signature: &str, path: &str| (signature.to_string(), path.to_string());
+
+        assert_eq!(signatures(items[0].information.get_implementations().unwrap()), vec![
+            pair("impl Shape for Field", ""),
+            pair("impl crate::Shape for P", "shapes::Point"),
+        ]);
+        let point = &items[1].information.get_content().unwrap()[0];
+        assert_eq!(signatures(point.information.get_implementations().unwrap()), vec![
+            pair("impl Point", "shapes::Point"),
+            pair("impl crate::Shape for P", "shapes::Point"),
+            pair("impl Eq for P", "shapes::Point"),
+        ]);
+        assert!(matches!(&items[2].information, Info::Module { impls, .. } if impls.is_empty()));
+    }
+
+    #[test]
+    fn method_anchors() {
+        use askama::Template;
+
+        let mut items = vec![item(Type::Struct, "Point", struct_info("", vec![], vec![], &[]), 1)];
+        link_implementations(&mut items, &[], vec![
+            implementation("impl Eq for Point", Some("Eq"), "Point", vec![method("eq", 4)], 3),
+            implementation("impl Point<A>", None, "Point", vec![method("new", 8), method("eq", 9)], 7),
+            implementation("impl Point<B>", None, "Point", vec![method("new", 12)], 11),
+        ]);
+
+        // the first inherent definition keeps the plain id, the trait implementations are listed last
+        let html = Structure::new(&items[0], &ModulePage::crate_root(PathBuf::from("doc"), PathBuf::from("src")), "../assets").render().unwrap();
+        let ids = html.split("id=\"").skip(1).map(|rest| &rest[..rest.find('"').unwrap()]).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["method.new", "method.eq", "method.new-1", "method.eq-1"]);
+
+        let links = DocLinks::new(&items, &[], LinkStyle::Pages("html"));
+        assert_eq!(links.rewrite("[Point::new] and [Point::eq]", &[], &[]).0, "[Point::new](struct.Point.html#method.new) and [Point::eq](struct.Point.html#method.eq)");
+
+        let index = search_index("anchors", &items, &links);
+        assert_eq!(index.iter().map(|entry| entry.link.as_str()).collect::<Vec<_>>(), vec!["struct.Point.html", "struct.Point.html#method.eq-1", "struct.Point.html#method.new", "struct.Point.html#method.eq", "struct.Point.html#method.new-1"]);
+    }
+
+    #[test]
+    fn broken_links() {
+        let output_dir = std::env::temp_dir().join("doc_generator_broken_links");
+        let _ = std::fs::remove_dir_all(&output_dir);
+        std::fs::create_dir_all(output_dir.join("my_crate").join("src")).unwrap();
+
+        let write = |path: &str, text: &str| std::fs::write(output_dir.join(path), text).unwrap();
+        write("my_crate/index.html", r#"<a href="fn.main.html">main</a> <a href="fn.missing.html">missing</a> <a href="https://noir-lang.org">noir</a>"#);
+        write("my_crate/fn.main.html", r#"<a class="source-link" href="src/main.nr.html#L2-L4">source</a> <a href="src/main.nr.html#L9">line</a> <a href="index.html#top">top</a>"#);
+        write("my_crate/src/main.nr.html", r#"<tr id="L1"></tr><tr id="L2"></tr><link rel="stylesheet" href="../../assets/style.css">"#);
+        write("my_crate/search-index.js", r#"window.searchIndex = [{"link":"fn.main.html"},{"link":"struct.Gone.html"}];"#);
+
+        let broken_links = check_links(&output_dir).unwrap().into_iter().map(|link| link.to_string()).collect::<Vec<_>>();
+        let page = |name: &str| output_dir.join("my_crate").join(name).display().to_string();
+
+        assert_eq!(broken_links, vec![
+            format!("{}: broken link `src/main.nr.html#L9`", page("fn.main.html")),
+            format!("{}: broken link `index.html#top`", page("fn.main.html")),
+            format!("{}: broken link `fn.missing.html`", page("index.html")),
+            format!("{}: broken link `struct.Gone.html`", page("search-index.js")),
+            format!("{}: broken link `../../assets/style.css`", page("src/main.nr.html")),
+        ]);
+    }
+
+    #[test]
+    fn doc_comment_tests() {
+        let mut span = SourceSpan::new(PathBuf::from("input_files/layout.nr"), 20, 22);
+        span.doc.lines = (6..20).collect();
+        let doc = " Adds two numbers.\n\n ```noir\n # let x = 1;\n assert(add(x, 2) == 3);\n ```\n\n ```noir,ignore\n add(1)\n ```\n\n ```rust\n not noir\n ```";
+        let add = Output { r#type: Type::Function, name: "add".to_string(), doc: doc.to_string(), information: Info::Function { signature: "fn add(x: Field, y: Field) -> Field".to_string(), attributes: FunctionAttributes::default() }, visibility: ItemVisibility::Public, span: span.clone() };
+        let shapes = Output { r#type: Type::Module, name: "shapes".to_string(), doc: String::new(), information: Info::Module { content: vec![add], imports: vec![], impls: vec![] }, visibility: ItemVisibility::Public, span };
+
+        let tests = doc_tests("layout", &[shapes]);
+        assert_eq!(tests.iter().map(|test| test.to_string()).collect::<Vec<_>>(), vec!["layout::shapes::add (input_files/layout.nr:9)", "layout::shapes::add (input_files/layout.nr:14)"]);
+        assert_eq!(tests[0].code, "let x = 1;\nassert(add(x, 2) == 3);");
+        assert_eq!(tests[0].program(), ("fn main() {\nlet x = 1;\nassert(add(x, 2) == 3);\n}\n".to_string(), 1));
+        assert!(tests[1].ignore);
+        let krate = TestCrate { name: "layout".to_string(), entry: PathBuf::from("input_files/layout.nr"), files: vec![] };
+        assert_eq!(tests[1].run(&krate), DocTestResult::Ignored);
+
+        assert_eq!(hide_doc_test_lines("```noir,should_fail\n# use dep::std;\nfoo();\n```"), "```noir\nfoo();\n```");
+    }
+
+    #[test]
+    fn checked_doc_tests() {
+        let entry = Path::new("input_files/doc_tests.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+        let krate = TestCrate::new("doc_tests", entry, &items).unwrap();
+
+        let results = doc_tests("doc_tests", &items).iter().map(|test| (test.to_string(), test.run(&krate))).collect::<Vec<_>>();
+        let failure = |location: &str, result: &DocTestResult| matches!(result, DocTestResult::Failed(err) if err.starts_with(&format!("input_files/doc_tests.nr:{}: ", location)));
+
+        assert_eq!(results.iter().map(|(test, _)| test.as_str()).collect::<Vec<_>>(), vec![
+            "doc_tests::add (input_files/doc_tests.nr:4)",
+            "doc_tests::add (input_files/doc_tests.nr:14)",
+            "doc_tests::sub (input_files/doc_tests.nr:27)",
+            "doc_tests::mul (input_files/doc_tests.nr:40)",
+            "doc_tests::mul (input_files/doc_tests.nr:44)",
+        ]);
+        // the crate items are in scope through `dep::doc_tests`
+        assert_eq!(results[0].1, DocTestResult::Passed);
+        // the type error is what the `should_fail` test expects
+        assert_eq!(results[1].1, DocTestResult::Passed);
+        // type and resolver errors are reported at their lines
+        assert!(failure("30", &results[2].1));
+        assert!(failure("40", &results[3].1));
+        // code that does not parse fails even if it should fail
+        assert!(failure("44", &results[4].1));
+    }
+
+    #[test]
+    fn documentation_coverage() {
+        let documented = |function: Function, doc: &str| Function { doc: doc.to_string(), ..function };
+
+        let point = Output {
+            doc: "A point.".to_string(),
+            ..item(Type::Struct, "Point", struct_info(
+                "",
+                vec![Field { doc: "The x coordinate.".to_string(), ..field("x", 5) }, field("y", 6)],
+                vec![
+                    implementation("impl Point", None, "Point", vec![documented(method("new", 10), "Creates a point."), documented(method("len", 12), " ")], 9),
+                    implementation("impl Eq for Point", Some("Eq"), "Point", vec![method("eq", 16)], 15),
+                ],
+                &[],
+            ), 4)
+        };
+        let shape = item(Type::Trait, "Shape", trait_info(vec![documented(method("area", 21), "The area.")]), 20);
+        let shapes = module("shapes", vec![point, shape], 3);
+        let main = Output { doc: "Entry point.".to_string(), ..function("main", FunctionAttributes::default(), 1) };
+
+        let coverage = PackageCoverage::new("layout", &[main, shapes]);
+
+        assert_eq!(coverage.modules.iter().map(|module| (module.path.as_str(), module.counts.total())).collect::<Vec<_>>(), vec![
+            ("layout", Count { documented: 1, total: 1 }),
+            ("layout::shapes", Count { documented: 4, total: 7 }),
+        ]);
+        assert_eq!(coverage.total.fields, Count { documented: 1, total: 2 });
+        assert_eq!(coverage.total.impl_methods, Count { documented: 1, total: 2 });
+        assert_eq!(coverage.total.trait_methods, Count { documented: 1, total: 1 });
+        assert_eq!(coverage.coverage, 62.5);
+
+        let table = coverage.table();
+        assert_eq!(table.lines().next(), Some("Module          Functions  Structs  Fields  Traits  Trait methods  Impl methods  Coverage"));
+        assert_eq!(table.lines().last(), Some("Total                 1/1      1/1     1/2     0/1            1/1           1/2     62.5%"));
+
+        // the `//!` comments of a struct document it, like for the `missing_docs` lint
+        let mut circle = item(Type::Struct, "Circle", struct_info("", vec![], vec![], &[]), 30);
+        if let Info::Struct { additional_doc, .. } = &mut circle.information {
+            *additional_doc = " A circle.".to_string();
+        }
+        assert_eq!(PackageCoverage::new("circle", &[circle]).total.structs, Count { documented: 1, total: 1 });
+    }
+
+    #[test]
+    fn doc_lints() {
+        let file = std::env::temp_dir().join("doc_generator_lints.nr");
+        std::fs::write(&file, "").unwrap();
+
+        let span = SourceSpan::new(file.clone(), 4, 6);
+        let function = |name: &str, doc: &str, visibility: ItemVisibility| Output { r#type: Type::Function, name: name.to_string(), doc: doc.to_string(), information: Info::Function { signature: format!("fn {}()", name), attributes: FunctionAttributes::default() }, visibility, span: span.clone() };
+        let point = Output {
+            r#type: Type::Struct,
+            name: "Point".to_string(),
+            doc: " A point.\n\n ```noir\n let p = Point { x: 1 };".to_string(),
+            information: Info::Struct { signature: String::new(), additional_doc: String::new(), fields: vec![Field { name: "x".to_string(), r#type: "Field".to_string(), doc: String::new(), visibility: ItemVisibility::Public, span: SourceSpan::new(file.clone(), 11, 11) }], implementations: vec![], attributes: vec![], deprecated: None },
+            visibility: ItemVisibility::Public,
+            span: SourceSpan { doc: DocSpan { file: file.clone(), lines: (6..10).collect() }, ..SourceSpan::new(file.clone(), 10, 12) },
+        };
+        let items = [function("main", "", ItemVisibility::Public), function("helper", "", ItemVisibility::Private), function("documented", "Docs.", ItemVisibility::Public), point];
+
+        let mut levels = LintLevels::default();
+        levels.set(&[Lint::MissingDocs], Level::Deny);
+        let diagnostics = lint_crate(&file, &items, &levels).unwrap().iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(diagnostics, vec![
+            format!("error[missing_docs]: {}:4: public function `main` is not documented", file.display()),
+            format!("warning[unbalanced_code_fences]: {}:8: code block is not closed", file.display()),
+            format!("error[missing_docs]: {}:11: public field `Point::x` is not documented", file.display()),
+        ]);
+
+        levels.set(&[Lint::MissingDocs, Lint::UnbalancedCodeFences], Level::Allow);
+        assert!(lint_crate(&file, &items, &levels).unwrap().is_empty());
+    }
+
+    #[test]
+    fn doc_comment_lines() {
+        let file = Path::new("input_files/doc_lines.nr");
+        let items = Output::to_output(&get_doc(file).unwrap(), &ModuleLocation::crate_root(file)).unwrap();
+
+        // the attribute, the blank line and the comment between the doc comments are skipped
+        assert_eq!(items[0].span.doc.lines, vec![1, 2, 3, 4, 5, 9]);
+        assert_eq!(items[1].span.doc.lines, (14..20).collect::<Vec<_>>());
+
+        let tests = doc_tests("doc_lines", &items);
+        assert_eq!(tests.iter().map(|test| test.to_string()).collect::<Vec<_>>(), vec!["doc_lines::add (input_files/doc_lines.nr:4)", "doc_lines::sub (input_files/doc_lines.nr:18)"]);
+
+        let diagnostics = lint_crate(file, &items, &LintLevels::default()).unwrap().iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<_>>();
+        assert_eq!(diagnostics, vec!["warning[unbalanced_code_fences]: input_files/doc_lines.nr:27: code block is not closed"]);
+    }
+
+    #[test]
+    fn doc_comment_lints() {
+        use noirc_frontend::{hir::resolution::errors::Span, token::{DocComments, Keyword, SpannedToken, Token}};
+
+        let text = "fn a() {}\n//! stray\n      ;\n/** block */\n/// line\nfn b() {}\n///";
+        let token = |token: Token, start: u32| SpannedToken::new(token, Span::inclusive(start, start));
+        let tokens = vec![
+            token(Token::Keyword(Keyword::Fn), 0), token(Token::Ident("a".to_string()), 3), token(Token::LeftParen, 4), token(Token::RightParen, 5), token(Token::LeftBrace, 7), token(Token::RightBrace, 8),
+            token(Token::DocComment(DocComments::Outer(" stray".to_string())), 10),
+            token(Token::Semicolon, 26),
+            token(Token::DocComment(DocComments::Block(" block ".to_string())), 28),
+            token(Token::DocComment(DocComments::Single(" line".to_string())), 41),
+            token(Token::Keyword(Keyword::Fn), 50), token(Token::Ident("b".to_string()), 53), token(Token::LeftParen, 54), token(Token::RightParen, 55), token(Token::LeftBrace, 57), token(Token::RightBrace, 58),
+            token(Token::DocComment(DocComments::Single(String::new())), 60),
+        ];
+
+        let diagnostics = lint_tokens(Path::new("src/main.nr"), text, &tokens, &LintLevels::default());
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<_>>(), vec![
+            "warning[misplaced_inner_docs]: src/main.nr:2: `//!` comment documents neither a module nor an item",
+            "warning[mixed_doc_comments]: src/main.nr:4: doc comment mixes block and line comments",
+            "warning[empty_docs]: src/main.nr:7: doc comment contains only whitespace",
+        ]);
+
+        let log = sarif(&diagnostics);
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["runs"][0]["results"][1]["ruleId"], "mixed_doc_comments");
+        assert_eq!(log["runs"][0]["results"][1]["locations"][0]["physicalLocation"]["region"]["startLine"], 4);
+    }
+
+    #[test]
+    fn module_page_sections() {
+        use askama::Template;
+
+        let test = FunctionAttributes { is_test: true, ..FunctionAttributes::default() };
+        let module = AllOutput {
+            all_output: vec![function("main", FunctionAttributes::default(), 1), item(Type::Struct, "Point", struct_info("", vec![], vec![], &[]), 3), function("check", test, 8)],
+            filename: "layout".to_string(),
+            span: span(1),
+            source_link: String::new(),
+            assets: String::new(),
+            search_page: String::new(),
+        };
+        let html = module.render().unwrap();
+        let headings = html.lines().filter_map(|line| line.trim().strip_prefix("<h1 class=\"h1\">")?.strip_suffix("</h1>")).collect::<Vec<_>>();
+
+        // the same sections as in the markdown output, the empty ones are left out
+        assert_eq!(headings, vec!["Functions", "Structs", "Tests"]);
+        assert!(html.contains("href=\"fn.main.html\"") && html.contains("href=\"struct.Point.html\""));
+    }
+
+    #[test]
+    fn test_functions() {
+        let private = |function: Output| Output { visibility: ItemVisibility::Private, ..function };
+
+        let test = FunctionAttributes { attributes: vec!["#[test(should_fail)]".to_string()], modifiers: vec![], is_test: true, deprecated: None };
+        let oracle = FunctionAttributes { attributes: vec!["#[oracle(get_notes)]".to_string()], modifiers: vec!["unconstrained".to_string()], is_test: false, deprecated: None };
+        assert_eq!(oracle.badges(), vec!["unconstrained".to_string(), "#[oracle(get_notes)]".to_string()]);
+
+        let tests = module("tests", vec![private(function("fails", test.clone(), 9)), private(function("helper", FunctionAttributes::default(), 12))], 7);
+        let items = vec![private(function("get_notes", oracle, 2)), private(function("check", test, 4)), tests];
+        assert!(items[1].is_test());
+
+        let items = without_tests(items);
+        assert_eq!(items.iter().map(|item| item.name.as_str()).collect::<Vec<_>>(), vec!["get_notes", "tests"]);
+        assert_eq!(module_functions(&items, "tests"), vec!["helper".to_string()]);
+    }
+
+    #[test]
+    fn parsed_function_attributes() {
+        let entry = Path::new("input_files/attributes.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+        let attributes = |attributes: &[&str], modifiers: &[&str], is_test: bool| FunctionAttributes {
+            attributes: attributes.iter().map(|attribute| attribute.to_string()).collect(),
+            modifiers: modifiers.iter().map(|modifier| modifier.to_string()).collect(),
+            is_test,
+            deprecated: None,
+        };
+
+        assert_eq!(items[0].information, Info::Function { signature: "unconstrained fn get_notes(owner: Field) -> [Field; 2]".to_string(), attributes: attributes(&["#[oracle(get_notes)]"], &["unconstrained"], false) });
+        assert_eq!(items[1].information, Info::Function { signature: "fn main(x: Field) -> pub Field".to_string(), attributes: attributes(&[], &["pub return"], false) });
+        assert_eq!(items[2].information.get_attributes(), Some(attributes(&["#[test]"], &[], true)));
+        assert_eq!(items[3].information.get_attributes(), Some(attributes(&["#[test(should_fail)]"], &[], true)));
+
+        let items = without_tests(items);
+        assert_eq!(items.iter().map(|item| item.name.as_str()).collect::<Vec<_>>(), vec!["get_notes", "main", "tests"]);
+        assert_eq!(module_functions(&items, "tests"), vec!["helper".to_string()]);
+    }
+
+    #[test]
+    fn deprecations() {
+        let deprecated = FunctionAttributes { deprecated: Some("Use [Point::new] instead.".to_string()), ..FunctionAttributes::default() };
+        let new = Function { signature: "fn new() -> Point".to_string(), ..method("new", 5) };
+        let point = item(Type::Struct, "Point", Info::Struct { signature: String::new(), additional_doc: String::new(), fields: vec![], implementations: vec![implementation("impl Point", None, "Point", vec![new], 4)], attributes: vec![], deprecated: Some(String::new()) }, 2);
+        let origin = Output { information: Info::Function { signature: "fn origin() -> Point".to_string(), attributes: deprecated }, ..function("origin", FunctionAttributes::default(), 9) };
+
+        let mut items = vec![point, origin];
+        assert!(items.iter().all(Output::is_deprecated));
+
+        let links = DocLinks::new(&items, &[], LinkStyle::Pages("html"));
+        let index = search_index("layout", &items, &links);
+        assert_eq!(index.iter().map(|entry| (entry.name.as_str(), entry.deprecated)).collect::<Vec<_>>(), vec![("Point", true), ("new", false), ("origin", true)]);
+
+        links.resolve_items(&mut items, &[]);
+        assert_eq!(items[1].information.get_deprecated(), Some("Use [Point::new](struct.Point.html#method.new) instead.".to_string()));
+
+        let json = serde_json::to_value(JsonCrate::new("layout".to_string(), &items, &span(1), Path::new("src"))).unwrap();
+        assert_eq!(json["root"]["items"][0]["deprecated"], "");
+        assert_eq!(json["root"]["items"][0]["impls"][0]["methods"][0].get("deprecated"), None);
+        assert_eq!(json["root"]["items"][1]["deprecated"], "Use [Point::new](struct.Point.html#method.new) instead.");
+        assert_eq!(json["root"]["items"][1]["span"], serde_json::json!({ "file": "main.nr", "start_line": 9, "end_line": 9 }));
+    }
+
+    #[test]
+    fn parsed_deprecations() {
+        let entry = Path::new("input_files/deprecated.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+
+        assert_eq!(items.iter().map(|item| (item.name.as_str(), item.information.get_deprecated())).collect::<Vec<_>>(), vec![
+            ("Point", Some("use Vec2 instead".to_string())),
+            ("Vec2", None),
+            ("point", Some("use `Vec2` instead".to_string())),
+        ]);
+        // the deprecation is a notice, not one of the attribute badges
+        assert_eq!(items[0].information.get_struct_attributes(), Some(vec![]));
+        assert_eq!(items[2].information.get_attributes().unwrap().badges(), Vec::<String>::new());
+
+        let methods = &items[0].information.get_implementations().unwrap()[0].functions;
+        assert_eq!(methods.iter().map(|method| (method.name.as_str(), method.attributes.deprecated.clone())).collect::<Vec<_>>(), vec![
+            ("new", Some(String::new())),
+            ("origin", None),
+        ]);
+
+        let links = DocLinks::new(&items, &[], LinkStyle::Pages("html"));
+        let index = search_index("deprecated", &items, &links);
+        assert_eq!(index.iter().filter(|entry| entry.deprecated).map(|entry| entry.name.as_str()).collect::<Vec<_>>(), vec!["Point", "new", "point"]);
+    }
+
+    #[test]
+    fn contract_items() {
+        assert_eq!(attribute_name("#[aztec(private)]"), "private");
+        assert_eq!(attribute_name("#[public]"), "public");
+        assert_eq!(attribute_name("#[oracle(get_notes)]"), "oracle");
+
+        let contract_function = |name: &str, attributes: &[&str], modifiers: &[&str], line: usize| Output {
+            visibility: ItemVisibility::Private,
+            ..function(name, FunctionAttributes { attributes: attributes.iter().map(|attribute| attribute.to_string()).collect(), modifiers: modifiers.iter().map(|modifier| modifier.to_string()).collect(), ..FunctionAttributes::default() }, line)
+        };
+        let structure = |name: &str, attributes: &[&str], implementations: Vec<Implementation>, line: usize| Output {
+            visibility: ItemVisibility::Private,
+            ..item(Type::Struct, name, struct_info("", vec![], implementations, attributes), line)
+        };
+        let note_interface = implementation("impl NoteInterface for ValueNote", Some("NoteInterface"), "ValueNote", vec![], 40);
+
+        let items = contract_visibility(vec![
+            contract_function("constructor", &["#[aztec(private)]", "#[aztec(initializer)]"], &[], 10),
+            contract_function("transfer", &["#[aztec(public)]"], &[], 15),
+            contract_function("balance_of", &[], &["unconstrained"], 20),
+            contract_function("_reduce", &["#[aztec(public)]"], &["internal"], 24),
+            contract_function("helper", &["#[contract_library_method]"], &[], 28),
+            structure("Storage", &[], vec![], 3),
+            structure("Transfer", &["#[event]"], vec![], 32),
+            structure("ValueNote", &[], vec![note_interface], 36),
+            structure("Config", &[], vec![], 44),
+        ]);
+        let visibility = |name: &str| items.iter().find(|item| item.name == name).unwrap().visibility;
+        assert_eq!(visibility("constructor"), ItemVisibility::Public);
+        assert_eq!(visibility("Transfer"), ItemVisibility::Public);
+        assert_eq!(visibility("helper"), ItemVisibility::Private);
+        assert_eq!(visibility("Config"), ItemVisibility::Private);
+
+        let sections = contract_sections(&items).into_iter()
+            .map(|section| (section.title, section.items.iter().map(|item| item.name.clone()).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        assert_eq!(sections, vec![
+            ("Storage".to_string(), names(&["Storage"])),
+            ("Private functions".to_string(), names(&["constructor"])),
+            ("Public functions".to_string(), names(&["transfer", "_reduce"])),
+            ("Unconstrained and view functions".to_string(), names(&["balance_of"])),
+            ("Initializers".to_string(), names(&["constructor"])),
+            ("Internal functions".to_string(), names(&["_reduce"])),
+            ("Other functions".to_string(), names(&["helper"])),
+            ("Events".to_string(), names(&["Transfer"])),
+            ("Notes".to_string(), names(&["ValueNote"])),
+            ("Structs".to_string(), names(&["Config"])),
+        ]);
+
+        let contract = Output { r#type: Type::Contract, ..module("Token", items, 1) };
+        assert_eq!(contract.link(), "Token/index.html");
+
+        let json = serde_json::to_value(JsonCrate::new("layout".to_string(), &[contract], &span(1), Path::new("src"))).unwrap();
+        assert_eq!(json["root"]["items"][0]["kind"], "contract");
+        assert_eq!(json["root"]["items"][0]["items"][6]["attributes"][0], "#[event]");
+    }
+
+    #[test]
+    fn parsed_contract() {
+        let entry = Path::new("input_files/contract.nr");
+        let items = Output::to_output(&get_doc(entry).unwrap(), &ModuleLocation::crate_root(entry)).unwrap();
+
+        assert_eq!((items[0].r#type, items[0].name.as_str(), items[0].doc.as_str()), (Type::Contract, "Token", " A token with public balances."));
+        let content = items[0].information.get_content().unwrap();
+
+        // the interface of the contract is public without `pub`
+        let visibility = |name: &str| content.iter().find(|item| item.name == name).unwrap().visibility;
+        assert_eq!(visibility("constructor"), ItemVisibility::Public);
+        assert_eq!(visibility("Storage"), ItemVisibility::Public);
+        assert_eq!(visibility("Transfer"), ItemVisibility::Public);
+        assert_eq!(visibility("helper"), ItemVisibility::Private);
+
+        let sections = contract_sections(&content).into_iter()
+            .map(|section| (section.title, section.items.iter().map(|item| item.name.clone()).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let section = |title: &str, names: &[&str]| (title.to_string(), names.iter().map(|name| name.to_string()).collect::<Vec<_>>());
+        assert_eq!(sections, vec![
+            section("Storage", &["Storage"]),
+            section("Private functions", &["constructor"]),
+            section("Public functions", &["mint_public", "mint", "_increase"]),
+            section("Unconstrained and view functions", &["balance_of"]),
+            section("Initializers", &["constructor"]),
+            section("Internal functions", &["_increase"]),
+            section("Other functions", &["helper"]),
+            section("Events", &["Transfer"]),
+            section("Globals", &["DECIMALS"]),
+            section("Type aliases", &["Amount"]),
+            section("Tests", &["test_mint"]),
+        ]);
+
+        let mint = content.iter().find(|item| item.name == "mint").unwrap();
+        assert_eq!(mint.information.get_attributes().unwrap().attributes, vec!["#[aztec(public)]".to_string()]);
+        assert_eq!(mint.information.get_deprecated(), Some("use mint_public instead".to_string()));
+    }
+
+    #[test]
+    fn public_items() {
+        let visible_function = |name: &str, visibility: ItemVisibility, line: usize| Output { visibility, ..function(name, FunctionAttributes::default(), line) };
+        let visible_method = |name: &str, visibility: ItemVisibility, line: usize| Function { visibility, ..method(name, line) };
+        let visible_field = |name: &str, visibility: ItemVisibility, line: usize| Field { visibility, ..field(name, line) };
+
+        let point = item(Type::Struct, "Point", struct_info(
+            "struct Point {\n    pub x: Field,\n    pub(crate) y: Field,\n}",
+            vec![visible_field("x", ItemVisibility::Public, 5), visible_field("y", ItemVisibility::Crate, 6)],
+            vec![
+                implementation("impl Point", None, "Point", vec![visible_method("new", ItemVisibility::Public, 10), visible_method("check", ItemVisibility::Private, 12)], 9),
+                implementation("impl Point", None, "Point", vec![visible_method("helper", ItemVisibility::Crate, 17)], 16),
+            ],
+            &[],
+        ), 4);
+        let shapes = module("shapes", vec![point, visible_function("area", ItemVisibility::Crate, 20)], 3);
+        let items = vec![visible_function("main", ItemVisibility::Private, 1), visible_function("hash", ItemVisibility::Public, 2), shapes];
+
+        let public = public_api(items);
+        assert_eq!(public.iter().map(|item| item.name.as_str()).collect::<Vec<_>>(), vec!["hash", "shapes"]);
+
+        let content = public[1].information.get_content().unwrap();
+        assert_eq!(content.iter().map(|item| item.name.as_str()).collect::<Vec<_>>(), vec!["Point"]);
+        assert_eq!(content[0].information.get_signature().unwrap(), "struct Point {\n    pub x: Field,\n    /* private fields */\n}");
+        assert_eq!(content[0].information.get_fields().unwrap().len(), 1);
+
+        let implementations = content[0].information.get_implementations().unwrap();
+        assert_eq!(implementations.len(), 1);
+        assert_eq!(implementations[0].functions.iter().map(|function| function.name.as_str()).collect::<Vec<_>>(), vec!["new"]);
     }
 }